@@ -0,0 +1,44 @@
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand)]
+pub enum BackupCommand {
+    #[command(about = "List the Backup hook replicas of a path")]
+    List {
+        #[arg(value_name = "PATH_ID", help = "ID of the path")]
+        path_id: Option<String>,
+    },
+    #[command(about = "Remove the replicas not kept by the Backup hook retention")]
+    Prune {
+        #[arg(value_name = "PATH_ID", help = "ID of the path")]
+        path_id: Option<String>,
+
+        #[arg(long, help = "Only show the replicas that would be removed")]
+        dry_run: bool,
+    },
+    #[command(about = "Restore a Backup hook replica")]
+    Restore {
+        #[arg(value_name = "PATH_ID", help = "ID of the path")]
+        path_id: Option<String>,
+
+        #[arg(long, value_name = "N", help = "Replica number to restore")]
+        replica: Option<u32>,
+
+        #[arg(
+            long,
+            value_name = "TIME",
+            conflicts_with = "replica",
+            help = "Restore the latest replica taken at or before a time (7d, YYYY-MM-DD or RFC 3339)"
+        )]
+        at: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "DIR",
+            help = "Restore into this path instead of local_path"
+        )]
+        target: Option<String>,
+
+        #[arg(short = 'y', long, help = "Overwrite the target without asking")]
+        yes: bool,
+    },
+}
@@ -0,0 +1,55 @@
+use crate::{
+    cli::{
+        commands::{backup::utils, path::utils::path},
+        context::CommandContext,
+    },
+    log_warn,
+};
+use anyhow::Context;
+use console::Style;
+
+#[derive(Clone, Debug)]
+pub struct LocalArgs<'a> {
+    pub path_id: &'a Option<String>,
+}
+
+pub fn backup_list(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    let path_id = match context.local.path_id {
+        Some(value) => value.clone(),
+        None => {
+            path::Prompt::path_config("Select the path:", std::sync::Arc::clone(&context.registry))
+                .context("failed to select path")?
+        }
+    };
+
+    let path_config = context
+        .with_registry()?
+        .paths
+        .iter()
+        .find(|p| p.id == path_id)
+        .ok_or_else(|| anyhow::anyhow!("path does not exists"))?
+        .clone();
+
+    let remote_config = context
+        .with_registry()?
+        .remotes
+        .iter()
+        .find(|r| r.id == path_config.remote_id)
+        .ok_or_else(|| anyhow::anyhow!("remote does not exists"))?
+        .clone();
+
+    let replicas = utils::list_replicas(&path_config, &context.global.rclone, &remote_config)?;
+
+    if replicas.is_empty() {
+        log_warn!("no replicas found for {}", path_config.local_path);
+        return Ok(());
+    }
+
+    let idx_style = Style::new().bold().cyan();
+
+    for replica in &replicas {
+        println!("{} {}", idx_style.apply_to(">"), replica);
+    }
+
+    Ok(())
+}
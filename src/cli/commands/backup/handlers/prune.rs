@@ -0,0 +1,97 @@
+use crate::{
+    cli::{
+        commands::{backup::utils::replicas::Replica, path::utils::path},
+        context::CommandContext,
+    },
+    config::prelude::HookConfig,
+    hooks::backup::BackupHook,
+    log_info, log_success,
+};
+use anyhow::Context;
+use console::Style;
+
+#[derive(Clone, Debug)]
+pub struct LocalArgs<'a> {
+    pub path_id: &'a Option<String>,
+    pub dry_run: &'a bool,
+}
+
+pub fn backup_prune(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    let path_id = match context.local.path_id {
+        Some(value) => value.clone(),
+        None => {
+            path::Prompt::path_config("Select the path:", std::sync::Arc::clone(&context.registry))
+                .context("failed to select path")?
+        }
+    };
+
+    let path_config = context
+        .with_registry()?
+        .paths
+        .iter()
+        .find(|p| p.id == path_id)
+        .ok_or_else(|| anyhow::anyhow!("path does not exists"))?
+        .clone();
+
+    let remote_config = context
+        .with_registry()?
+        .remotes
+        .iter()
+        .find(|r| r.id == path_config.remote_id)
+        .ok_or_else(|| anyhow::anyhow!("remote does not exists"))?
+        .clone();
+
+    let dry_run = *context.local.dry_run;
+    let rclone = &context.global.rclone;
+    let mut pruned = Vec::new();
+
+    for hook in path_config.hooks.push.iter().chain(&path_config.hooks.pull) {
+        let HookConfig::Backup(cfg) = hook else {
+            continue;
+        };
+
+        let hook = BackupHook::from(cfg.clone());
+
+        for backup_type in &hook.types {
+            let Some(location) = hook.location(*backup_type) else {
+                continue;
+            };
+
+            let removed = hook
+                .prune_replicas(
+                    *backup_type,
+                    rclone,
+                    &remote_config,
+                    dry_run,
+                    &context.config,
+                )
+                .with_context(|| format!("failed to prune {} replicas", backup_type))?;
+
+            pruned.extend(removed.into_iter().map(|replica| Replica {
+                backup_type: *backup_type,
+                location: location.to_string(),
+                processed: false,
+                object_name: None,
+                replica,
+            }));
+        }
+    }
+
+    if pruned.is_empty() {
+        log_info!("no replicas to prune for {}", path_config.local_path);
+        return Ok(());
+    }
+
+    let idx_style = Style::new().bold().red();
+
+    for replica in &pruned {
+        println!("{} {}", idx_style.apply_to("-"), replica);
+    }
+
+    match dry_run {
+        true => log_info!("{} replica(s) would be removed", pruned.len()),
+        false => log_success!("{} replica(s) removed", pruned.len()),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use crate::{
+    cli::{
+        commands::{
+            backup::utils::{self, replicas},
+            path::utils::path,
+            sync::utils as sync_utils,
+        },
+        context::CommandContext,
+    },
+    config::prelude::HookConfig,
+    hooks::{
+        artifacts::{self, DOWNLOAD_PREFIX},
+        prelude::{HookContext, HookContextMetadata},
+    },
+    log_debug, log_info, log_success, log_warn,
+    utils::schedule::parse_time,
+};
+use anyhow::Context;
+
+#[derive(Clone, Debug)]
+pub struct LocalArgs<'a> {
+    pub path_id: &'a Option<String>,
+    pub replica: &'a Option<u32>,
+    pub at: &'a Option<String>,
+    pub target: &'a Option<String>,
+    pub yes: &'a bool,
+}
+
+pub fn backup_restore(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    let path_id = match context.local.path_id {
+        Some(value) => value.clone(),
+        None => path::Prompt::path_config(
+            "Select the path to restore:",
+            std::sync::Arc::clone(&context.registry),
+        )
+        .context("failed to select path")?,
+    };
+
+    let path_config = context
+        .with_registry()?
+        .paths
+        .iter()
+        .find(|p| p.id == path_id)
+        .ok_or_else(|| anyhow::anyhow!("path does not exists"))?
+        .clone();
+
+    let remote_config = context
+        .with_registry()?
+        .remotes
+        .iter()
+        .find(|r| r.id == path_config.remote_id)
+        .ok_or_else(|| anyhow::anyhow!("remote does not exists"))?
+        .clone();
+
+    let replicas = utils::list_replicas(&path_config, &context.global.rclone, &remote_config)?;
+
+    if replicas.is_empty() {
+        log_warn!("no replicas found for {}", path_config.local_path);
+        return Ok(());
+    }
+
+    let at = context
+        .local
+        .at
+        .as_deref()
+        .map(|at| parse_time(at).map(|dt| dt.timestamp()))
+        .transpose()?;
+
+    let replica = match (context.local.replica, at) {
+        (None, None) => {
+            let options: Vec<String> = replicas.iter().map(|r| r.to_string()).collect();
+
+            let selected = inquire::Select::new("Select the replica to restore:", options)
+                .with_vim_mode(true)
+                .raw_prompt()
+                .context("failed to select replica")?;
+
+            &replicas[selected.index]
+        }
+        (replica_number, at) => utils::select_replica(&replicas, *replica_number, at)
+            .context("no replica matches the given criteria")?,
+    };
+
+    let target = match context.local.target {
+        Some(target) => std::path::PathBuf::from(shellexpand::tilde(target).to_string()),
+        None => std::path::PathBuf::from(&path_config.local_path),
+    };
+
+    log_info!("restoring {} into {:?}", replica, target);
+
+    if target.exists()
+        && !*context.local.yes
+        && !inquire::Confirm::new(&format!("{:?} will be replaced, continue?", target))
+            .with_default(false)
+            .prompt()
+            .context("failed to get confirmation")?
+    {
+        log_warn!("restore aborted by user");
+        return Ok(());
+    }
+
+    let temp_dir = artifacts::create_dir(DOWNLOAD_PREFIX, &context.config)
+        .context("failed to create temp directory")?;
+
+    let fetched = replicas::fetch_replica(
+        replica,
+        &context.global.rclone,
+        &remote_config,
+        temp_dir.path(),
+        &context.config,
+    )
+    .context("failed to fetch replica")?;
+
+    let content = replicas::replica_content(replica, &fetched)?;
+
+    log_debug!("replica content: {:?}", content);
+
+    // the hook run owns the processed content until it is in place
+    let run = match replica.processed {
+        true => {
+            let hooks: Vec<HookConfig> = path_config
+                .hooks
+                .pull
+                .iter()
+                .rev()
+                .filter(|h| !matches!(h, HookConfig::Backup(_) | HookConfig::Command(_)))
+                .cloned()
+                .collect();
+
+            log_info!("running {} pull hook(s) over the replica", hooks.len());
+
+            Some(sync_utils::execute_hooks(
+                HookContext::new(
+                    content.clone(),
+                    &context.global.rclone,
+                    &remote_config,
+                    &path_config,
+                )
+                .with_metadata(
+                    HookContextMetadata::SourceLocalPath,
+                    &path_config.local_path,
+                )
+                .with_metadata(
+                    HookContextMetadata::SourceRemotePath,
+                    &path_config.remote_path,
+                ),
+                &hooks,
+                &context.config,
+            )?)
+        }
+        false => None,
+    };
+
+    let content = run.as_ref().map_or(content, |run| run.context.path.clone());
+
+    replicas::replace_target(&content, &target)?;
+
+    log_success!("restored {} into {:?}", replica, target);
+
+    Ok(())
+}
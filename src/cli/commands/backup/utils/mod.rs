@@ -0,0 +1,3 @@
+pub mod replicas;
+
+pub use super::utils::replicas::{list_replicas, select_replica};
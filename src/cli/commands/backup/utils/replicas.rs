@@ -0,0 +1,328 @@
+use crate::{
+    config::prelude::{AppConfig, HookConfig, HookExecType, PathConfig, Remote},
+    hooks::backup::{
+        BackupHook, BackupType,
+        backup_hook::BackupHookReplica,
+        incremental::{MANIFESTS_DIR, local, manifest::manifest_name, remote},
+    },
+    log_debug, log_warn,
+};
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+
+/// A replica created by one of the Backup hooks of a path.
+#[derive(Debug)]
+pub struct Replica {
+    pub backup_type: BackupType,
+
+    /// Local directory or remote path holding the replicas.
+    pub location: String,
+
+    /// Whether the replica holds content processed by hooks (e.g. zipped),
+    /// so the pull hooks must run to get the original content back.
+    pub processed: bool,
+
+    /// Name of the backed up object inside remote replicas, `None` for local ones.
+    pub object_name: Option<String>,
+
+    pub replica: BackupHookReplica,
+}
+
+impl std::fmt::Display for Replica {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let date = chrono::DateTime::from_timestamp(self.replica.timestamp as i64, 0)
+            .map(|dt| {
+                dt.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+
+        let file_name = match self.backup_type {
+            BackupType::LocalIncremental | BackupType::RemoteIncremental => format!(
+                "{}/{}",
+                MANIFESTS_DIR,
+                manifest_name(self.replica.timestamp, self.replica.replica_number)
+            ),
+            _ => format!("{}.{}", self.replica.timestamp, self.replica.replica_number),
+        };
+
+        write!(
+            f,
+            "#{} {} {} ({}/{})",
+            self.replica.replica_number,
+            date,
+            self.backup_type,
+            self.location.trim_end_matches('/'),
+            file_name
+        )
+    }
+}
+
+/// Whether the content reaching a Backup hook was already transformed by earlier hooks.
+///
+/// Remote replicas copy what was pushed, so they are processed. Other replicas
+/// taken on push are processed only if a preceding hook rewrote the content,
+/// and the ones taken on pull hold the untouched `local_path`.
+fn is_processed(hooks: &[HookConfig], index: usize, backup_type: BackupType) -> bool {
+    match (backup_type, hooks[index].exec_type()) {
+        (BackupType::Remote, _) => true,
+        (_, HookExecType::Push) => hooks[..index].iter().any(|h| h.modifies_filename()),
+        (_, HookExecType::Pull) => false,
+    }
+}
+
+/// Collects the replicas of every Backup hook of a path, newest first.
+pub fn list_replicas(
+    path_config: &PathConfig,
+    rclone: &str,
+    remote: &Remote,
+) -> anyhow::Result<Vec<Replica>> {
+    let mut replicas = Vec::new();
+    let remote_object = path_config.remote_object(&path_config.hooks.push);
+
+    for hooks in [&path_config.hooks.push, &path_config.hooks.pull] {
+        for (index, hook) in hooks.iter().enumerate() {
+            let HookConfig::Backup(cfg) = hook else {
+                continue;
+            };
+
+            let hook = BackupHook::from(cfg.clone());
+
+            for backup_type in &hook.types {
+                let Some(location) = hook.location(*backup_type) else {
+                    continue;
+                };
+
+                let found = hook.replicas(*backup_type, rclone, remote)?;
+
+                let processed = is_processed(hooks, index, *backup_type);
+                let object_name = match backup_type {
+                    BackupType::Remote => Path::new(&remote_object)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string()),
+                    _ => None,
+                };
+
+                replicas.extend(found.into_iter().map(|replica| Replica {
+                    backup_type: *backup_type,
+                    location: location.to_string(),
+                    processed,
+                    object_name: object_name.clone(),
+                    replica,
+                }));
+            }
+        }
+    }
+
+    replicas.sort_by_key(|r| std::cmp::Reverse(r.replica.timestamp));
+
+    log_debug!("replicas found: {:?}", replicas);
+
+    Ok(replicas)
+}
+
+/// Picks a replica by number or by time.
+///
+/// # Returns
+/// - With `replica_number`: the newest replica with that number, local ones first.
+/// - With `at`: the newest replica taken at or before that timestamp.
+/// - `None` when nothing matches, or no criteria was given.
+pub fn select_replica(
+    replicas: &[Replica],
+    replica_number: Option<u32>,
+    at: Option<i64>,
+) -> Option<&Replica> {
+    match (replica_number, at) {
+        (Some(number), _) => replicas
+            .iter()
+            .filter(|r| r.replica.replica_number == number)
+            .min_by_key(|r| match r.backup_type {
+                BackupType::Local | BackupType::LocalIncremental => 0,
+                BackupType::Remote | BackupType::RemoteIncremental => 1,
+            }),
+        (None, Some(at)) => replicas
+            .iter()
+            .filter(|r| r.replica.timestamp as i64 <= at)
+            .max_by_key(|r| r.replica.timestamp),
+        (None, None) => None,
+    }
+}
+
+/// Copies a replica into `temp_dir`, returning the path of the fetched content.
+pub fn fetch_replica(
+    replica: &Replica,
+    rclone: &str,
+    remote: &Remote,
+    temp_dir: &Path,
+    cfg: &AppConfig,
+) -> anyhow::Result<PathBuf> {
+    let file_name = format!(
+        "{}.{}",
+        replica.replica.timestamp, replica.replica.replica_number
+    );
+    let destination = temp_dir.join(&file_name);
+
+    match replica.backup_type {
+        BackupType::Local => {
+            if replica.replica.path.is_dir() {
+                std::fs::create_dir_all(&destination)
+                    .context("failed to create replica directory")?;
+
+                fs_extra::dir::copy(
+                    &replica.replica.path,
+                    &destination,
+                    &fs_extra::dir::CopyOptions::new().content_only(true),
+                )
+                .with_context(|| format!("failed to copy replica: {:?}", replica.replica.path))?;
+            } else {
+                std::fs::copy(&replica.replica.path, &destination).with_context(|| {
+                    format!("failed to copy replica: {:?}", replica.replica.path)
+                })?;
+            }
+        }
+        BackupType::Remote => {
+            let source = format!(
+                "{}:{}/{}",
+                remote.remote_name,
+                replica.location.trim_end_matches('/'),
+                file_name
+            );
+
+            let output = std::process::Command::new(rclone)
+                .args([
+                    "copyto",
+                    &source,
+                    destination
+                        .to_str()
+                        .context("failed to convert destination to str")?,
+                ])
+                .output()
+                .context("failed to execute rclone copyto")?;
+
+            if !output.status.success() {
+                anyhow::bail!(
+                    "failed to download replica {}: {}",
+                    source,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+        }
+        BackupType::LocalIncremental => {
+            local::restore_local_incremental_replica(
+                &replica.replica,
+                &replica.location,
+                &destination,
+            )
+            .context("failed to reassemble replica")?;
+        }
+        BackupType::RemoteIncremental => {
+            remote::restore_remote_incremental_replica(
+                &replica.replica,
+                &replica.location,
+                rclone,
+                remote,
+                &destination,
+                cfg,
+            )
+            .context("failed to reassemble replica")?;
+        }
+    }
+
+    Ok(destination)
+}
+
+/// Resolves the content of a fetched replica.
+///
+/// Local directory and incremental replicas wrap the backed up content in an entry
+/// named after it, so a single entry is unwrapped. Remote replicas wrap the object
+/// under its own name, the single entry is only unwrapped when it has that name,
+/// older replicas holding a directory content at their root are kept whole.
+pub fn replica_content(replica: &Replica, fetched: &Path) -> anyhow::Result<PathBuf> {
+    if !fetched.is_dir() {
+        return Ok(fetched.to_path_buf());
+    }
+
+    let entries: Vec<_> = std::fs::read_dir(fetched)
+        .with_context(|| format!("failed to read replica: {:?}", fetched))?
+        .filter_map(Result::ok)
+        .collect();
+
+    let [entry] = entries.as_slice() else {
+        return Ok(fetched.to_path_buf());
+    };
+
+    let unwrap = match replica.backup_type {
+        BackupType::Remote => replica
+            .object_name
+            .as_deref()
+            .is_some_and(|name| entry.file_name() == name),
+        _ => true,
+    };
+
+    match unwrap {
+        true => Ok(entry.path()),
+        false => Ok(fetched.to_path_buf()),
+    }
+}
+
+/// Prefix of the staging directory created next to the restore target.
+const RESTORE_STAGING_PREFIX: &str = ".rcloud-restore-";
+
+/// Replaces `target` with `content`, which can be a file or a directory.
+///
+/// The content is staged next to the target first, then swapped in with renames.
+/// The current content is only removed once the swap succeeded, a failed restore
+/// leaves the target untouched.
+pub fn replace_target(content: &Path, target: &Path) -> anyhow::Result<()> {
+    let parent = target
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+
+    std::fs::create_dir_all(parent).context("failed to create parent directory")?;
+
+    let staging = tempfile::Builder::new()
+        .prefix(RESTORE_STAGING_PREFIX)
+        .tempdir_in(parent)
+        .with_context(|| format!("failed to create staging directory in {:?}", parent))?;
+
+    let staged = staging.path().join("restored");
+    let previous = staging.path().join("previous");
+
+    if content.is_file() {
+        fs_extra::file::move_file(content, &staged, &fs_extra::file::CopyOptions::new())
+            .with_context(|| format!("failed to stage file in {:?}", staged))?;
+    } else {
+        std::fs::create_dir_all(&staged).context("failed to create staging directory")?;
+
+        fs_extra::dir::copy(
+            content,
+            &staged,
+            &fs_extra::dir::CopyOptions::new().content_only(true),
+        )
+        .with_context(|| format!("failed to stage directory in {:?}", staged))?;
+    }
+
+    let had_target = target.exists();
+
+    if had_target {
+        std::fs::rename(target, &previous)
+            .with_context(|| format!("failed to move current content aside: {:?}", target))?;
+    }
+
+    if let Err(e) = std::fs::rename(&staged, target) {
+        if had_target && let Err(restore_error) = std::fs::rename(&previous, target) {
+            log_warn!(
+                "current content was left in {:?}: {}",
+                staging.keep().join("previous"),
+                restore_error
+            );
+        }
+
+        return Err(e).with_context(|| format!("failed to move restored content to {:?}", target));
+    }
+
+    // dropping the staging directory removes the previous content
+    Ok(())
+}
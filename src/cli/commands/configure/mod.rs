@@ -1 +1 @@
-pub mod handlers;
\ No newline at end of file
+pub mod handlers;
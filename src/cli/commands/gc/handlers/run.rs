@@ -0,0 +1,66 @@
+use crate::{
+    cli::context::CommandContext,
+    hooks::artifacts::{remove, stale_artifacts},
+    log_info, log_success, log_warn,
+    utils::schedule::parse_interval,
+};
+use anyhow::Context;
+use console::Style;
+
+#[derive(Clone, Debug)]
+pub struct LocalArgs<'a> {
+    pub older_than: &'a String,
+    pub dry_run: &'a bool,
+}
+
+pub fn gc_run(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    let older_than = parse_interval(context.local.older_than)?
+        .to_std()
+        .context("invalid age")?;
+
+    let stale = stale_artifacts(&context.config, older_than)?;
+
+    if stale.is_empty() {
+        log_info!("no temp artifacts to remove");
+        return Ok(());
+    }
+
+    let dry_run = *context.local.dry_run;
+    let idx_style = Style::new().bold().red();
+    let size_style = Style::new().dim();
+
+    let mut removed = 0;
+    let mut freed = 0;
+
+    for artifact in &stale {
+        println!(
+            "{} {} {}",
+            idx_style.apply_to("-"),
+            artifact.path.display(),
+            size_style.apply_to(format!("{} bytes", artifact.size))
+        );
+
+        if dry_run {
+            continue;
+        }
+
+        match remove(&artifact.path) {
+            Ok(()) => {
+                removed += 1;
+                freed += artifact.size;
+            }
+            Err(e) => log_warn!("{:#}", e),
+        }
+    }
+
+    match dry_run {
+        true => log_info!(
+            "{} artifact(s) would be removed ({} bytes)",
+            stale.len(),
+            stale.iter().map(|a| a.size).sum::<u64>()
+        ),
+        false => log_success!("{} artifact(s) removed ({} bytes)", removed, freed),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use crate::{
+    cli::context::CommandContext,
+    log_warn,
+    utils::{
+        history::{HISTORY_FILE, History, SyncStatus},
+        prelude::directories,
+        schedule::parse_time,
+    },
+};
+use console::Style;
+
+#[derive(Clone, Debug)]
+pub struct LocalArgs<'a> {
+    pub path_id: &'a Option<String>,
+    pub since: &'a Option<String>,
+    pub limit: &'a usize,
+}
+
+pub fn history_list(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    let since = context
+        .local
+        .since
+        .as_deref()
+        .map(|since| parse_time(since).map(|dt| dt.timestamp()))
+        .transpose()?;
+
+    let entries: Vec<_> = History::new(&directories().data_dir.join(HISTORY_FILE))
+        .entries()?
+        .into_iter()
+        .filter(|e| {
+            context
+                .local
+                .path_id
+                .as_ref()
+                .is_none_or(|id| &e.path_id == id)
+        })
+        .filter(|e| since.is_none_or(|since| e.timestamp >= since))
+        .collect();
+
+    if entries.is_empty() {
+        log_warn!("no sync history found");
+        return Ok(());
+    }
+
+    let time_style = Style::new().dim();
+    let local_style = Style::new().green();
+    let remote_style = Style::new().blue();
+
+    let skip = entries.len().saturating_sub(*context.local.limit);
+
+    for entry in entries.iter().skip(skip) {
+        let status_style = match entry.status {
+            SyncStatus::Success => Style::new().green(),
+            SyncStatus::Unchanged => Style::new().dim(),
+            SyncStatus::Aborted => Style::new().yellow(),
+            SyncStatus::Failed => Style::new().red(),
+        };
+
+        let timestamp = chrono::DateTime::from_timestamp(entry.timestamp, 0)
+            .map(|dt| {
+                dt.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+
+        println!(
+            "{} {} {} {} {} {}",
+            time_style.apply_to(timestamp),
+            status_style.apply_to(format!("{:>9}", entry.status.to_string())),
+            Style::new().bold().apply_to(entry.direction),
+            local_style.apply_to(&entry.local_path),
+            remote_style.apply_to(&entry.remote),
+            time_style.apply_to(format!(
+                "{}ms{}",
+                entry.duration_ms,
+                entry
+                    .hash
+                    .as_ref()
+                    .map(|h| format!(" {}", &h[..h.len().min(12)]))
+                    .unwrap_or_default()
+            )),
+        );
+
+        if let Some(error) = &entry.error {
+            println!("  {}", Style::new().red().apply_to(error));
+        }
+    }
+
+    Ok(())
+}
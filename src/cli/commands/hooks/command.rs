@@ -0,0 +1,15 @@
+use clap::Subcommand;
+
+use crate::config::prelude::HookExecType;
+
+#[derive(Debug, Subcommand)]
+pub enum HooksCommand {
+    #[command(about = "Show what the hooks of a path would do, without changing anything")]
+    Explain {
+        #[arg(value_name = "PATH_ID", help = "ID of the path")]
+        path_id: Option<String>,
+
+        #[arg(long, value_enum, default_value_t = HookExecType::Push, help = "Sync direction")]
+        direction: HookExecType,
+    },
+}
@@ -0,0 +1,253 @@
+use crate::{
+    cli::{
+        commands::{path::utils::path, sync::utils as sync_utils},
+        context::CommandContext,
+    },
+    config::prelude::{Hook, HookConfig, HookExecType, SyncMode},
+    hooks::{
+        artifacts::{DOWNLOAD_PREFIX, temp_root},
+        prelude::{HookContext, HookContextMetadata},
+    },
+    log_info,
+    utils::filter::PathFilter,
+};
+use anyhow::Context;
+use console::Style;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug)]
+pub struct LocalArgs<'a> {
+    pub path_id: &'a Option<String>,
+    pub direction: &'a HookExecType,
+}
+
+/// Quotes an argument printed in a command line when the shell would split it.
+fn quote(arg: &str) -> String {
+    match arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || "'\"$\\*?".contains(c)) {
+        true => format!("'{}'", arg.replace('\'', r"'\''")),
+        false => arg.to_string(),
+    }
+}
+
+/// Whether the hook hands a new file or directory to the next one, whose content
+/// is only known once it ran.
+fn replaces_content(hook: &HookConfig) -> bool {
+    hook.modifies_filename() || matches!(hook, HookConfig::External(_))
+}
+
+pub fn hooks_explain(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    let path_id = match context.local.path_id {
+        Some(value) => value.clone(),
+        None => {
+            path::Prompt::path_config("Select the path:", std::sync::Arc::clone(&context.registry))
+                .context("failed to select path")?
+        }
+    };
+
+    let path_config = context
+        .with_registry()?
+        .paths
+        .iter()
+        .find(|p| p.id == path_id)
+        .ok_or_else(|| anyhow::anyhow!("path does not exists"))?
+        .clone();
+
+    let remote_config = context
+        .with_registry()?
+        .remotes
+        .iter()
+        .find(|r| r.id == path_config.remote_id)
+        .ok_or_else(|| anyhow::anyhow!("remote does not exists"))?
+        .clone();
+
+    let direction = *context.local.direction;
+    let hooks = path_config.hooks.by_exec(direction);
+
+    // pull hooks undo the push ones, they run in reverse order
+    let chain: Vec<HookConfig> = match direction {
+        HookExecType::Push => hooks.to_vec(),
+        HookExecType::Pull => hooks.iter().rev().cloned().collect(),
+    };
+
+    let base_name = Path::new(&path_config.remote_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("archive");
+
+    // a pull downloads the object named by all of its hooks
+    let download_name = sync_utils::compute_remote_filename(hooks, base_name);
+    let temp = temp_root(&context.config)?;
+
+    let remote = |object: &str| format!("{}:{}", remote_config.remote_name, object);
+    let download_dir = temp.join(format!("{}*", DOWNLOAD_PREFIX));
+
+    let mut ctx = HookContext::new(
+        match direction {
+            HookExecType::Push => PathBuf::from(&path_config.local_path),
+            HookExecType::Pull => download_dir.join(&download_name),
+        },
+        &context.global.rclone,
+        &remote_config,
+        &path_config,
+    )
+    .with_metadata(
+        HookContextMetadata::SourceLocalPath,
+        &path_config.local_path,
+    )
+    .with_metadata(
+        HookContextMetadata::SourceRemotePath,
+        &path_config.remote_path,
+    );
+
+    let idx_style = Style::new().bold().cyan();
+    let dim_style = Style::new().dim();
+    let error_style = Style::new().red();
+
+    println!(
+        "{} {} {} {}",
+        Style::new().bold().apply_to(direction),
+        Style::new().green().apply_to(&path_config.local_path),
+        dim_style.apply_to(if direction == HookExecType::Push {
+            "->"
+        } else {
+            "<-"
+        }),
+        Style::new()
+            .blue()
+            .apply_to(remote(&path_config.remote_path)),
+    );
+
+    if chain.is_empty() {
+        log_info!("no {} hooks, the content is synced as is", direction);
+    }
+
+    // hooks whose condition is met, a sync names and uploads the content after them
+    let mut applied: Vec<HookConfig> = Vec::new();
+
+    for (index, hook) in chain.iter().enumerate() {
+        println!("{} {}", idx_style.apply_to(format!("{}.", index + 1)), hook);
+
+        match sync_utils::evaluate_when(hook, &ctx) {
+            Ok(Some(true)) => println!("   {}", dim_style.apply_to("condition met, the hook runs")),
+            Ok(Some(false)) => {
+                println!(
+                    "   {}",
+                    dim_style.apply_to("condition not met, the hook is skipped")
+                );
+                continue;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                println!("   {}", error_style.apply_to(format!("{:#}", e)));
+                continue;
+            }
+        }
+
+        applied.push(hook.clone());
+
+        let processor: Box<dyn Hook> = Box::from(hook.clone());
+
+        match processor.explain(&ctx, &context.config) {
+            Ok(lines) => {
+                for line in lines {
+                    println!("   {}", line);
+                }
+            }
+            Err(e) => println!("   {}", error_style.apply_to(format!("{:#}", e))),
+        }
+
+        if direction == HookExecType::Push && replaces_content(hook) {
+            ctx.path = temp
+                .join("rcloud-*")
+                .join(sync_utils::compute_remote_filename(&applied, base_name));
+        }
+    }
+
+    let named_by = match direction {
+        HookExecType::Push => applied.as_slice(),
+        HookExecType::Pull => hooks,
+    };
+
+    let renamed = named_by.iter().any(|h| h.modifies_filename());
+    let final_name = sync_utils::compute_remote_filename(named_by, base_name);
+    let multipart = sync_utils::is_multipart(named_by);
+
+    let mut rclone_args = context
+        .config
+        .rclone_args(&remote_config, &path_config)
+        .context("failed to resolve rclone args")?;
+
+    if !renamed {
+        rclone_args.extend(
+            PathFilter::from_path_config(&path_config)
+                .context("failed to build path filter")?
+                .rclone_args(),
+        );
+    }
+
+    let (mode, source, target) = match direction {
+        HookExecType::Push => {
+            // hooks producing new content leave it in a temp dir, renamed to the remote name
+            let source = match applied
+                .iter()
+                .any(|h| !matches!(h, HookConfig::Backup(_) | HookConfig::Command(_)))
+            {
+                true => temp.join("rcloud-*").join(&final_name),
+                false => PathBuf::from(&path_config.local_path),
+            };
+
+            let target = match multipart {
+                true => path_config.remote_object(&applied),
+                false => path_config.remote_path.clone(),
+            };
+
+            (
+                path_config.mode,
+                source.display().to_string(),
+                remote(&target),
+            )
+        }
+        HookExecType::Pull => {
+            let target = match (renamed, multipart) {
+                (true, true) => download_dir.join(&final_name),
+                _ => download_dir,
+            };
+
+            (
+                SyncMode::Copy,
+                remote(&path_config.remote_object(hooks)),
+                target.display().to_string(),
+            )
+        }
+    };
+
+    println!();
+    println!(
+        "{} {}",
+        dim_style.apply_to("remote name:"),
+        match renamed {
+            true => final_name.as_str(),
+            false => "unchanged, the content is synced into the remote path",
+        }
+    );
+
+    println!(
+        "{} {} {}",
+        dim_style.apply_to("rclone:"),
+        quote(&context.global.rclone),
+        sync_utils::rclone_command_args(&mode, &source, &target, &rclone_args)
+            .iter()
+            .map(|arg| quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+
+    if direction == HookExecType::Pull && path_config.mode == SyncMode::Move {
+        println!(
+            "{} the remote content is deleted once it is in place",
+            dim_style.apply_to("then:")
+        );
+    }
+
+    Ok(())
+}
@@ -1,4 +1,12 @@
+pub mod backup;
 pub mod configure;
+pub mod gc;
+pub mod history;
+pub mod hooks;
 pub mod path;
 pub mod remote;
+pub mod schedule;
+pub mod status;
 pub mod sync;
+pub mod verify;
+pub mod watch;
@@ -1,5 +1,7 @@
 use clap::Subcommand;
 
+use crate::config::prelude::SyncMode;
+
 #[derive(Debug, Subcommand)]
 pub enum PathCommand {
     List,
@@ -12,6 +14,12 @@ pub enum PathCommand {
 
         #[arg(long)]
         remote_path: Option<String>,
+
+        #[arg(long, value_enum, help = "Sync mode")]
+        mode: Option<SyncMode>,
+
+        #[arg(long, help = "Cron expression or interval (e.g. \"every 6h\")")]
+        schedule: Option<String>,
     },
     Remove {
         #[arg(long)]
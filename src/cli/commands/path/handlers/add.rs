@@ -1,6 +1,6 @@
 use crate::{
     cli::{
-        commands::path::utils::{hooks, path, tags},
+        commands::path::utils::{filters, hooks, path, schedule, tags},
         context::CommandContext,
     },
     config::prelude::*,
@@ -14,6 +14,8 @@ pub struct LocalArgs<'a> {
     pub remote_id: &'a Option<String>,
     pub local_path: &'a Option<String>,
     pub remote_path: &'a Option<String>,
+    pub mode: &'a Option<SyncMode>,
+    pub schedule: &'a Option<String>,
 }
 
 impl<'a> Default for LocalArgs<'a> {
@@ -22,6 +24,8 @@ impl<'a> Default for LocalArgs<'a> {
             remote_id: &None,
             local_path: &None,
             remote_path: &None,
+            mode: &None,
+            schedule: &None,
         }
     }
 }
@@ -58,6 +62,15 @@ pub fn path_add(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
             .context("failed to get remote path")?,
     };
 
+    let mode = match context.local.mode {
+        Some(value) => *value,
+        None => SyncMode::select("Sync mode:")
+            .with_vim_mode(true)
+            .with_help_message("Mirror deletes files on the target that no longer exist")
+            .prompt()
+            .context("failed to select sync mode")?,
+    };
+
     log_debug!(
         "{} -> (remote_id: {}):{}",
         local_path,
@@ -67,17 +80,34 @@ pub fn path_add(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
 
     let (push, pull) = hooks::declare_hooks().context("failed to get hooks")?;
 
+    let (include, exclude) = filters::declare_filters().context("failed to get filters")?;
+
     let tags = tags::declare_tags(std::sync::Arc::clone(&context.registry))
         .context("failed to get tags")?;
 
+    let schedule = match context.local.schedule {
+        Some(value) => Some(schedule::validate_schedule(value)?),
+        None => schedule::declare_schedule().context("failed to get schedule")?,
+    };
+
     let path_config = PathConfig {
         id: uuid::Uuid::new_v4().to_string(),
         remote_id: remote_id.clone(),
         local_path: local_path.clone(),
         remote_path: remote_path.clone(),
         hash: None,
+        hash_version: None,
+        hash_algorithm: None,
         hooks: PathConfigHooks { push, pull },
         tags,
+        schedule,
+        mode,
+        max_delete: None,
+        include,
+        exclude,
+        ignore_file: None,
+        rclone_args: None,
+        rclone_preset: None,
     };
 
     log_debug!("using path_config: {:?}", path_config);
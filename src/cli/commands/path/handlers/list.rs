@@ -1,4 +1,11 @@
-use crate::{cli::context::CommandContext, log_warn};
+use crate::{
+    cli::context::CommandContext,
+    log_warn,
+    utils::{
+        history::{HISTORY_FILE, History, SyncStatus},
+        prelude::directories,
+    },
+};
 use console::Style;
 
 pub fn path_list(context: CommandContext) -> anyhow::Result<()> {
@@ -14,14 +21,44 @@ pub fn path_list(context: CommandContext) -> anyhow::Result<()> {
     let hooks_style = Style::new().bold().magenta();
     let tags_style = Style::new().dim().italic();
 
+    let last_syncs = History::new(&directories().data_dir.join(HISTORY_FILE))
+        .last_by_path()
+        .unwrap_or_else(|e| {
+            log_warn!("could not read sync history: {}", e);
+            Default::default()
+        });
+
     for (i, path) in context.with_registry()?.paths.iter().enumerate() {
         let tags_display = match path.tags.is_empty() {
             true => String::new(),
             false => format!(" [tags: {}]", path.tags.join(", ")),
         };
 
+        let last_sync = match last_syncs.get(&path.id) {
+            None => Style::new().dim().apply_to(String::from(" [never synced]")),
+            Some(entry) => {
+                let style = match entry.status {
+                    SyncStatus::Failed => Style::new().red(),
+                    SyncStatus::Aborted => Style::new().yellow(),
+                    SyncStatus::Success | SyncStatus::Unchanged => Style::new().dim(),
+                };
+
+                style.apply_to(format!(
+                    " [last {}: {} {}]",
+                    entry.direction.to_string().to_lowercase(),
+                    entry.status,
+                    chrono::DateTime::from_timestamp(entry.timestamp, 0)
+                        .map(|dt| dt
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string())
+                        .unwrap_or_default()
+                ))
+            }
+        };
+
         println!(
-            "{} {} {} {} {}{}",
+            "{} {} {} {} {}{}{}",
             idx_style.apply_to(format!("> {}.", i + 1)),
             local_style.apply_to(&path.local_path),
             arrow_style.apply_to("->"),
@@ -31,6 +68,7 @@ pub fn path_list(context: CommandContext) -> anyhow::Result<()> {
                 path.hooks.pull.len() + path.hooks.push.len()
             )),
             tags_style.apply_to(tags_display),
+            last_sync,
         );
     }
 
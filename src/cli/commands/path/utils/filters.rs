@@ -0,0 +1,41 @@
+use anyhow::Context;
+use inquire::{Confirm, Text};
+
+fn patterns(message: &str, help: &str) -> anyhow::Result<Vec<String>> {
+    let patterns = Text::new(message)
+        .with_help_message(help)
+        .prompt_skippable()
+        .context("failed to get patterns")?;
+
+    Ok(patterns
+        .map(|s| {
+            s.split(',')
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+pub fn declare_filters() -> anyhow::Result<(Vec<String>, Vec<String>)> {
+    let add_filters = Confirm::new("Add include/exclude filters?")
+        .with_default(false)
+        .with_help_message("a .rcloudignore file in the local path is always honored")
+        .prompt()
+        .context("failed to get confirmation")?;
+
+    if !add_filters {
+        return Ok((vec![], vec![]));
+    }
+
+    let include = patterns("Include patterns:", "comma-separated, glob only, optional")
+        .context("failed to get include patterns")?;
+
+    let exclude = patterns(
+        "Exclude patterns:",
+        "comma-separated, gitignore syntax, optional",
+    )
+    .context("failed to get exclude patterns")?;
+
+    Ok((include, exclude))
+}
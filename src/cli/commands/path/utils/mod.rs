@@ -1,3 +1,5 @@
+pub mod filters;
 pub mod hooks;
 pub mod path;
+pub mod schedule;
 pub mod tags;
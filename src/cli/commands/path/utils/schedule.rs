@@ -0,0 +1,32 @@
+use crate::utils::schedule::Schedule;
+use anyhow::Context;
+use inquire::Text;
+
+pub fn validate_schedule(schedule: &str) -> anyhow::Result<String> {
+    schedule
+        .parse::<Schedule>()
+        .with_context(|| format!("invalid schedule: {}", schedule))?;
+
+    Ok(schedule.trim().to_string())
+}
+
+pub fn declare_schedule() -> anyhow::Result<Option<String>> {
+    let schedule = Text::new("Schedule:")
+        .with_help_message("cron expression or interval like `every 6h`, optional")
+        .with_validator(|input: &str| {
+            Ok(
+                match input.trim().is_empty() || input.parse::<Schedule>().is_ok() {
+                    true => inquire::validator::Validation::Valid,
+                    false => inquire::validator::Validation::Invalid(
+                        "expected a cron expression or `every <N><s|m|h|d|w>`".into(),
+                    ),
+                },
+            )
+        })
+        .prompt_skippable()
+        .context("failed to get schedule")?;
+
+    Ok(schedule
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty()))
+}
@@ -50,6 +50,8 @@ pub fn remote_add(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
                 id: Uuid::new_v4().to_string(),
                 remote_name: remote_name.clone(),
                 provider: provider.clone(),
+                rclone_args: None,
+                rclone_preset: None,
             })
         })
         .context("error inside transaction")?;
@@ -0,0 +1,17 @@
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand)]
+pub enum ScheduleCommand {
+    #[command(about = "List scheduled paths with their last and next run")]
+    List,
+    #[command(about = "Push scheduled paths when they are due")]
+    Run {
+        #[arg(long, help = "Run due paths once and exit")]
+        once: bool,
+    },
+    #[command(about = "Install systemd user units running the scheduler (Linux)")]
+    Install {
+        #[arg(long, help = "Print the units instead of writing them")]
+        print: bool,
+    },
+}
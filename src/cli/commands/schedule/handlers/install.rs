@@ -0,0 +1,57 @@
+use crate::{
+    cli::{commands::schedule::utils::units, context::CommandContext},
+    log_info, log_success,
+};
+use anyhow::Context;
+
+#[derive(Clone, Debug)]
+pub struct LocalArgs<'a> {
+    pub print: &'a bool,
+}
+
+pub fn schedule_install(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    if !cfg!(target_os = "linux") {
+        anyhow::bail!("schedule install is only supported on Linux (systemd)");
+    }
+
+    let executable = std::env::current_exe().context("failed to resolve rcloud executable")?;
+
+    let service = units::service_unit(&units::exec_start(&executable, &context.global));
+    let timer = units::timer_unit();
+
+    if *context.local.print {
+        println!(
+            "# {}\n{}\n# {}\n{}",
+            units::SERVICE_UNIT,
+            service,
+            units::TIMER_UNIT,
+            timer
+        );
+        return Ok(());
+    }
+
+    let unit_dir = directories::BaseDirs::new()
+        .context("failed to resolve home directory")?
+        .config_dir()
+        .join("systemd")
+        .join("user");
+
+    std::fs::create_dir_all(&unit_dir)
+        .with_context(|| format!("failed to create unit directory: {:?}", unit_dir))?;
+
+    for (name, content) in [(units::SERVICE_UNIT, service), (units::TIMER_UNIT, timer)] {
+        let unit_path = unit_dir.join(name);
+
+        std::fs::write(&unit_path, content)
+            .with_context(|| format!("failed to write unit: {:?}", unit_path))?;
+
+        log_info!("wrote {}", unit_path.display());
+    }
+
+    log_success!(
+        "units installed, enable them with: systemctl --user daemon-reload && systemctl --user enable --now {}",
+        units::TIMER_UNIT
+    );
+
+    Ok(())
+}
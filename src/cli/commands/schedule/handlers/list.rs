@@ -0,0 +1,72 @@
+use crate::{
+    cli::{
+        commands::schedule::utils::{ScheduleState, state::SCHEDULE_STATE_FILE},
+        context::CommandContext,
+    },
+    log_warn,
+    utils::{prelude::directories, schedule::Schedule},
+};
+use console::Style;
+
+pub fn schedule_list(context: CommandContext) -> anyhow::Result<()> {
+    let state = ScheduleState::load(&directories().data_dir.join(SCHEDULE_STATE_FILE))?;
+    let now = chrono::Local::now();
+
+    let registry = context.with_registry()?;
+    let scheduled: Vec<_> = registry
+        .paths
+        .iter()
+        .filter(|p| p.schedule.is_some())
+        .collect();
+
+    if scheduled.is_empty() {
+        log_warn!("no scheduled paths");
+        return Ok(());
+    }
+
+    let local_style = Style::new().green();
+    let schedule_style = Style::new().bold().yellow();
+    let time_style = Style::new().dim();
+
+    for path in scheduled {
+        let schedule = path.schedule.as_deref().unwrap_or_default();
+        let last_run = state.last_run(&path.id);
+
+        let next_run = match schedule.parse::<Schedule>() {
+            Ok(parsed) => parsed
+                .next_run(last_run, now)
+                .map(|next| next.max(now).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|e| format!("error: {}", e)),
+            Err(e) => format!("invalid: {}", e),
+        };
+
+        println!(
+            "{} {} {}",
+            local_style.apply_to(&path.local_path),
+            schedule_style.apply_to(format!("[{}]", schedule)),
+            time_style.apply_to(format!(
+                "last: {}, next: {}",
+                last_run
+                    .map(|l| l.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| String::from("never")),
+                next_run
+            )),
+        );
+
+        if let Some(error) = state.runs.get(&path.id).and_then(|r| r.last_error.as_ref()) {
+            let failed_at = state
+                .failed_at(&path.id)
+                .map(|f| f.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| String::from("unknown"));
+
+            println!(
+                "  {}",
+                Style::new()
+                    .red()
+                    .apply_to(format!("failed at {}: {}", failed_at, error))
+            );
+        }
+    }
+
+    Ok(())
+}
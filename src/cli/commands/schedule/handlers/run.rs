@@ -0,0 +1,131 @@
+use crate::{
+    cli::{
+        commands::{
+            schedule::utils::{
+                ScheduleState,
+                state::{SCHEDULE_LOCK_FILE, SCHEDULE_STATE_FILE},
+            },
+            sync::utils as sync_utils,
+        },
+        context::CommandContext,
+    },
+    config::prelude::Registry,
+    log_debug, log_error, log_info, log_success, log_warn,
+    utils::{history::SyncStatus, prelude::directories, schedule::Schedule},
+};
+use anyhow::Context;
+use fs2::FileExt;
+
+const TICK: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Delay before a failed push is retried, the schedule keeps counting from the last success.
+const RETRY_DELAY: chrono::TimeDelta = chrono::TimeDelta::minutes(5);
+
+#[derive(Clone, Debug)]
+pub struct LocalArgs<'a> {
+    pub once: &'a bool,
+}
+
+/// Pushes every scheduled path whose next run is due.
+fn run_due(context: &CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    let mut state = ScheduleState::load(&directories().data_dir.join(SCHEDULE_STATE_FILE))?;
+    let now = chrono::Local::now();
+
+    let scheduled: Vec<(String, String, String)> = context
+        .with_registry()?
+        .paths
+        .iter()
+        .filter_map(|p| {
+            p.schedule
+                .as_ref()
+                .map(|s| (p.id.clone(), p.local_path.clone(), s.clone()))
+        })
+        .collect();
+
+    for (path_id, local_path, schedule) in scheduled {
+        let schedule = match schedule.parse::<Schedule>() {
+            Ok(schedule) => schedule,
+            Err(e) => {
+                log_error!("invalid schedule for {}: {}", local_path, e);
+                continue;
+            }
+        };
+
+        match schedule.is_due(state.last_run(&path_id), now) {
+            Ok(true) => {}
+            Ok(false) => {
+                log_debug!("{} is not due yet", local_path);
+                continue;
+            }
+            Err(e) => {
+                log_error!("failed to compute next run for {}: {}", local_path, e);
+                continue;
+            }
+        }
+
+        if state
+            .failed_at(&path_id)
+            .is_some_and(|failed_at| now - failed_at < RETRY_DELAY)
+        {
+            log_debug!("{} failed recently, retrying later", local_path);
+            continue;
+        }
+
+        log_info!("running scheduled push for {}", local_path);
+
+        match sync_utils::push_path(context, &path_id) {
+            Ok(outcome) if outcome.status == SyncStatus::Aborted => {
+                state.record_failure(
+                    &path_id,
+                    String::from("mirror aborted, the deletions were not confirmed"),
+                )?;
+                log_error!("scheduled push aborted for {}", local_path);
+            }
+            Ok(_) => {
+                state.record_success(&path_id)?;
+                log_success!("scheduled push finished for {}", local_path);
+            }
+            Err(e) => {
+                state.record_failure(&path_id, e.to_string())?;
+                log_error!("scheduled push failed for {}: {}", local_path, e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn schedule_run(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    let lock_path = directories().data_dir.join(SCHEDULE_LOCK_FILE);
+
+    let lock_file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("failed to open schedule lock: {:?}", lock_path))?;
+
+    if lock_file.try_lock_exclusive().is_err() {
+        log_warn!("another scheduler run is in progress, skipping");
+        return Ok(());
+    }
+
+    loop {
+        run_due(&context)?;
+
+        if *context.local.once {
+            break;
+        }
+
+        std::thread::sleep(TICK);
+
+        // pick up paths added or changed while the loop was sleeping
+        let mut registry = context.with_registry()?;
+        let registry_path = registry.registry_path.clone();
+        *registry = Registry::load(&registry_path).context("failed to reload registry")?;
+    }
+
+    fs2::FileExt::unlock(&lock_file).context("failed to release schedule lock")?;
+
+    Ok(())
+}
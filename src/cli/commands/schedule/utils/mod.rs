@@ -0,0 +1,4 @@
+pub mod state;
+pub mod units;
+
+pub use super::utils::state::ScheduleState;
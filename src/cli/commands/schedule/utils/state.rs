@@ -0,0 +1,115 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+pub const SCHEDULE_STATE_FILE: &str = "schedule-state.json";
+pub const SCHEDULE_LOCK_FILE: &str = "schedule.lock";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduleRun {
+    /// Last successful run, the schedule counts from it.
+    #[serde(default)]
+    pub last_run: Option<i64>,
+
+    /// Last failed run since the last success, if any.
+    #[serde(default)]
+    pub failed_at: Option<i64>,
+
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+/// Last runs of every scheduled path, persisted in the data directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScheduleState {
+    #[serde(skip)]
+    state_path: PathBuf,
+
+    #[serde(default)]
+    pub runs: HashMap<String, ScheduleRun>,
+}
+
+impl ScheduleState {
+    pub fn load(state_path: &Path) -> anyhow::Result<Self> {
+        let state = match state_path.exists() {
+            true => {
+                let content = std::fs::read_to_string(state_path)
+                    .with_context(|| format!("failed to read schedule state: {:?}", state_path))?;
+
+                serde_json::from_str::<ScheduleState>(&content)
+                    .context("failed to parse schedule state")?
+            }
+            false => ScheduleState::default(),
+        };
+
+        Ok(Self {
+            state_path: state_path.to_path_buf(),
+            ..state
+        })
+    }
+
+    fn local_time(timestamp: Option<i64>) -> Option<chrono::DateTime<chrono::Local>> {
+        timestamp
+            .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+            .map(|dt| dt.with_timezone(&chrono::Local))
+    }
+
+    pub fn last_run(&self, path_id: &str) -> Option<chrono::DateTime<chrono::Local>> {
+        Self::local_time(self.runs.get(path_id).and_then(|run| run.last_run))
+    }
+
+    pub fn failed_at(&self, path_id: &str) -> Option<chrono::DateTime<chrono::Local>> {
+        Self::local_time(self.runs.get(path_id).and_then(|run| run.failed_at))
+    }
+
+    /// Records a successful run, the schedule counts from it.
+    pub fn record_success(&mut self, path_id: &str) -> anyhow::Result<()> {
+        self.runs.insert(
+            path_id.to_string(),
+            ScheduleRun {
+                last_run: Some(chrono::Utc::now().timestamp()),
+                failed_at: None,
+                last_error: None,
+            },
+        );
+
+        self.save()
+    }
+
+    /// Records a failed run, keeping the last successful one so the path stays due.
+    pub fn record_failure(&mut self, path_id: &str, error: String) -> anyhow::Result<()> {
+        let run = self.runs.entry(path_id.to_string()).or_default();
+
+        run.failed_at = Some(chrono::Utc::now().timestamp());
+        run.last_error = Some(error);
+
+        self.save()
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let contents =
+            serde_json::to_string_pretty(&self).context("failed to serialize schedule state")?;
+
+        let directory = self
+            .state_path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+
+        // written aside and renamed, an interrupted save keeps the previous state
+        let mut temp_file = tempfile::NamedTempFile::new_in(directory)
+            .with_context(|| format!("failed to create temp file in {:?}", directory))?;
+
+        std::io::Write::write_all(&mut temp_file, contents.as_bytes())
+            .with_context(|| format!("failed to write schedule state: {:?}", temp_file.path()))?;
+
+        temp_file
+            .persist(&self.state_path)
+            .with_context(|| format!("failed to replace schedule state: {:?}", self.state_path))?;
+
+        Ok(())
+    }
+}
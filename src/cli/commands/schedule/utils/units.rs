@@ -0,0 +1,51 @@
+use crate::cli::parser::GlobalParameters;
+
+pub const SERVICE_UNIT: &str = "rcloud-schedule.service";
+pub const TIMER_UNIT: &str = "rcloud-schedule.timer";
+
+/// Builds the `ExecStart` command, forwarding the global options of the current invocation.
+pub fn exec_start(executable: &std::path::Path, global: &GlobalParameters) -> String {
+    let mut args = vec![format!("\"{}\"", executable.display())];
+
+    if let Some(config) = &global.config {
+        args.push(format!("--config \"{}\"", config.display()));
+    }
+
+    if let Some(registry) = &global.registry {
+        args.push(format!("--registry \"{}\"", registry.display()));
+    }
+
+    args.push(format!("--rclone \"{}\"", global.rclone));
+    args.push(String::from("schedule run --once"));
+
+    args.join(" ")
+}
+
+pub fn service_unit(exec_start: &str) -> String {
+    format!(
+        "[Unit]\n\
+         Description=rcloud scheduled syncs\n\
+         \n\
+         [Service]\n\
+         Type=oneshot\n\
+         ExecStart={}\n",
+        exec_start
+    )
+}
+
+/// The timer fires every minute, `schedule run --once` decides which paths are due.
+pub fn timer_unit() -> String {
+    format!(
+        "[Unit]\n\
+         Description=Run rcloud scheduled syncs\n\
+         \n\
+         [Timer]\n\
+         OnCalendar=minutely\n\
+         Persistent=true\n\
+         Unit={}\n\
+         \n\
+         [Install]\n\
+         WantedBy=timers.target\n",
+        SERVICE_UNIT
+    )
+}
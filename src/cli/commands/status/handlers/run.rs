@@ -0,0 +1,395 @@
+use crate::{
+    cli::{commands::sync::utils as sync_utils, context::CommandContext},
+    config::prelude::{AppConfig, PathConfig, Remote},
+    log_warn,
+    utils::{
+        filter::PathFilter,
+        hash::HashOptions,
+        history::{HISTORY_FILE, History, HistoryEntry},
+        prelude::directories,
+    },
+};
+use anyhow::Context;
+use console::Style;
+use rayon::prelude::*;
+use std::path::Path;
+
+/// rclone exit code for a missing directory.
+const RCLONE_DIRECTORY_NOT_FOUND: i32 = 3;
+
+#[derive(Clone, Debug)]
+pub struct LocalArgs<'a> {
+    pub tags: &'a [String],
+    pub offline: &'a bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum LocalState {
+    Unchanged,
+    Modified,
+    Missing,
+    NeverSynced,
+    Error(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RemoteState {
+    Unchanged,
+    Changed,
+    Missing,
+    Unreachable(String),
+    Unknown,
+    Skipped,
+}
+
+impl std::fmt::Display for LocalState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocalState::Unchanged => write!(f, "unchanged"),
+            LocalState::Modified => write!(f, "modified"),
+            LocalState::Missing => write!(f, "missing"),
+            LocalState::NeverSynced => write!(f, "never synced"),
+            LocalState::Error(_) => write!(f, "error"),
+        }
+    }
+}
+
+impl std::fmt::Display for RemoteState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemoteState::Unchanged => write!(f, "unchanged"),
+            RemoteState::Changed => write!(f, "changed"),
+            RemoteState::Missing => write!(f, "missing"),
+            RemoteState::Unreachable(_) => write!(f, "unreachable"),
+            RemoteState::Unknown => write!(f, "unknown"),
+            RemoteState::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+struct Row {
+    path_id: String,
+    remote: String,
+    last_sync: Option<i64>,
+    local: LocalState,
+    remote_state: RemoteState,
+}
+
+/// Compares the local tree with the hash stored by the last sync.
+fn local_state(config: &AppConfig, path_config: &PathConfig) -> LocalState {
+    let local_path = Path::new(&path_config.local_path);
+
+    if !local_path.exists() {
+        return LocalState::Missing;
+    }
+
+    if path_config.hash.is_none() {
+        return LocalState::NeverSynced;
+    }
+
+    match sync_utils::stored_format_hash(path_config, local_path, &HashOptions::from(config)) {
+        Ok(hash) if path_config.hash.as_deref() == Some(hash.as_str()) => LocalState::Unchanged,
+        Ok(_) => LocalState::Modified,
+        Err(e) => LocalState::Error(format!("{:#}", e)),
+    }
+}
+
+/// Newest modification time of the remote files, as a unix timestamp.
+///
+/// # Returns
+/// - `Ok(Some(timestamp))` if the remote holds files, `Ok(Some(i64::MIN))` if it is empty.
+/// - `Ok(None)` if the remote path does not exist.
+/// - `Err` if rclone failed for any other reason (e.g. authentication or network).
+fn remote_newest_modtime(
+    rclone: &str,
+    config: &AppConfig,
+    remote: &Remote,
+    path_config: &PathConfig,
+) -> anyhow::Result<Option<i64>> {
+    let mut args = config
+        .rclone_args(remote, path_config)
+        .context("failed to resolve rclone args")?;
+
+    if !path_config.hooks.push.iter().any(|h| h.modifies_filename()) {
+        args.extend(
+            PathFilter::from_path_config(path_config)
+                .context("failed to build path filter")?
+                .rclone_args(),
+        );
+    }
+
+    let output = std::process::Command::new(rclone)
+        .args(["lsjson", "-R", "--files-only"])
+        .arg(format!(
+            "{}:{}",
+            remote.remote_name, path_config.remote_path
+        ))
+        .args(args)
+        .output()
+        .context("failed to execute rclone lsjson")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        return match output.status.code() {
+            Some(RCLONE_DIRECTORY_NOT_FOUND) => Ok(None),
+            _ if stderr.contains("not found") => Ok(None),
+            _ => anyhow::bail!("{}", stderr.trim()),
+        };
+    }
+
+    let entries: Vec<serde_json::Value> =
+        serde_json::from_slice(&output.stdout).context("failed to parse rclone lsjson output")?;
+
+    Ok(Some(
+        entries
+            .iter()
+            .filter_map(|e| e.get("ModTime").and_then(|v| v.as_str()))
+            .filter_map(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.timestamp())
+            .max()
+            .unwrap_or(i64::MIN),
+    ))
+}
+
+/// Compares the remote modification times with the last successful sync.
+///
+/// rclone keeps the modification time of uploaded files, so a file pushed from
+/// another machine only shows up if it was modified there after our last sync.
+fn remote_state(
+    rclone: &str,
+    config: &AppConfig,
+    remote: &Remote,
+    path_config: &PathConfig,
+    last_sync: Option<i64>,
+) -> RemoteState {
+    match remote_newest_modtime(rclone, config, remote, path_config) {
+        Err(e) => RemoteState::Unreachable(format!("{:#}", e)),
+        Ok(None) => RemoteState::Missing,
+        Ok(Some(_)) if last_sync.is_none() => RemoteState::Unknown,
+        Ok(Some(newest)) if last_sync.is_some_and(|t| newest > t) => RemoteState::Changed,
+        Ok(Some(_)) => RemoteState::Unchanged,
+    }
+}
+
+fn local_style(state: &LocalState) -> Style {
+    match state {
+        LocalState::Unchanged => Style::new().green(),
+        LocalState::Modified => Style::new().yellow(),
+        LocalState::NeverSynced => Style::new().dim(),
+        LocalState::Missing | LocalState::Error(_) => Style::new().red(),
+    }
+}
+
+fn remote_style(state: &RemoteState) -> Style {
+    match state {
+        RemoteState::Unchanged => Style::new().green(),
+        RemoteState::Changed => Style::new().yellow(),
+        RemoteState::Unknown | RemoteState::Skipped => Style::new().dim(),
+        RemoteState::Missing | RemoteState::Unreachable(_) => Style::new().red(),
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    let header = ["PATH", "LOCAL", "REMOTE", "LAST SYNC", "DESTINATION"];
+
+    let last_syncs: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            row.last_sync
+                .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                .map(|dt| {
+                    dt.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_else(|| String::from("-"))
+        })
+        .collect();
+
+    let width = |index: usize, values: &mut dyn Iterator<Item = usize>| {
+        values.max().unwrap_or(0).max(header[index].len())
+    };
+
+    let widths = [
+        width(0, &mut rows.iter().map(|r| r.path_id.len())),
+        width(1, &mut rows.iter().map(|r| r.local.to_string().len())),
+        width(
+            2,
+            &mut rows.iter().map(|r| r.remote_state.to_string().len()),
+        ),
+        width(3, &mut last_syncs.iter().map(String::len)),
+    ];
+
+    println!(
+        "{}",
+        Style::new().bold().apply_to(format!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {:<w3$}  {}",
+            header[0],
+            header[1],
+            header[2],
+            header[3],
+            header[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        ))
+    );
+
+    for (row, last_sync) in rows.iter().zip(&last_syncs) {
+        println!(
+            "{}  {}  {}  {}  {}",
+            Style::new()
+                .cyan()
+                .apply_to(format!("{:<w$}", row.path_id, w = widths[0])),
+            local_style(&row.local).apply_to(format!(
+                "{:<w$}",
+                row.local.to_string(),
+                w = widths[1]
+            )),
+            remote_style(&row.remote_state).apply_to(format!(
+                "{:<w$}",
+                row.remote_state.to_string(),
+                w = widths[2]
+            )),
+            Style::new()
+                .dim()
+                .apply_to(format!("{:<w$}", last_sync, w = widths[3])),
+            Style::new().blue().apply_to(&row.remote),
+        );
+    }
+}
+
+pub fn status_run(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    let registry = context.with_registry()?;
+
+    let paths: Vec<PathConfig> = registry
+        .paths
+        .iter()
+        .filter(|p| {
+            context.local.tags.is_empty() || p.tags.iter().any(|t| context.local.tags.contains(t))
+        })
+        .cloned()
+        .collect();
+
+    let remotes = registry.remotes.clone();
+    drop(registry);
+
+    if paths.is_empty() {
+        log_warn!("no paths configured");
+        return Ok(());
+    }
+
+    let last_syncs = History::new(&directories().data_dir.join(HISTORY_FILE))
+        .last_synced_by_path()
+        .unwrap_or_else(|e| {
+            log_warn!("could not read sync history: {}", e);
+            Default::default()
+        });
+
+    // each hash loads and saves the shared hash cache, paths are hashed one at a time
+    let local_states: Vec<LocalState> = paths
+        .iter()
+        .map(|path_config| local_state(&context.config, path_config))
+        .collect();
+
+    let rows: Vec<Row> = paths
+        .par_iter()
+        .zip(local_states)
+        .map(|(path_config, local)| {
+            let last_sync = last_syncs
+                .get(&path_config.id)
+                .map(|e: &HistoryEntry| e.timestamp);
+            let remote = remotes.iter().find(|r| r.id == path_config.remote_id);
+
+            let remote_state = match (remote, *context.local.offline) {
+                (_, true) => RemoteState::Skipped,
+                (None, false) => RemoteState::Unreachable(String::from("remote does not exists")),
+                (Some(remote), false) => remote_state(
+                    &context.global.rclone,
+                    &context.config,
+                    remote,
+                    path_config,
+                    last_sync,
+                ),
+            };
+
+            Row {
+                path_id: path_config.id.clone(),
+                remote: match remote {
+                    Some(remote) => format!("{}:{}", remote.remote_name, path_config.remote_path),
+                    None => path_config.remote_path.clone(),
+                },
+                last_sync,
+                local,
+                remote_state,
+            }
+        })
+        .collect();
+
+    print_table(&rows);
+
+    for row in &rows {
+        if let LocalState::Error(e) = &row.local {
+            log_warn!("{}: failed to hash local path: {}", row.path_id, e);
+        }
+
+        if let RemoteState::Unreachable(e) = &row.remote_state {
+            log_warn!("{}: remote unreachable: {}", row.path_id, e);
+        }
+    }
+
+    let hint = Style::new().dim();
+    let modified = rows
+        .iter()
+        .filter(|r| r.local == LocalState::Modified)
+        .count();
+    let changed = rows
+        .iter()
+        .filter(|r| r.remote_state == RemoteState::Changed)
+        .count();
+
+    let problems = rows
+        .iter()
+        .filter(|r| {
+            matches!(r.local, LocalState::Missing | LocalState::Error(_))
+                || matches!(
+                    r.remote_state,
+                    RemoteState::Missing | RemoteState::Unreachable(_)
+                )
+        })
+        .count();
+
+    println!();
+
+    if modified == 0 && changed == 0 && problems == 0 {
+        println!("nothing to sync, everything is up to date");
+    }
+
+    if modified > 0 {
+        println!(
+            "{} path(s) changed locally {}",
+            modified,
+            hint.apply_to("(use \"rcloud sync path <PATH_ID> --direction push\" to upload)")
+        );
+    }
+
+    if changed > 0 {
+        println!(
+            "{} path(s) changed on the remote {}",
+            changed,
+            hint.apply_to("(use \"rcloud sync path <PATH_ID> --direction pull\" to download)")
+        );
+    }
+
+    if problems > 0 {
+        println!(
+            "{} path(s) missing or unreachable {}",
+            problems,
+            hint.apply_to("(use \"rcloud verify\" to check them)")
+        );
+    }
+
+    Ok(())
+}
@@ -110,7 +110,8 @@ pub fn sync_single(
             },
             hooks: &hooks.push,
             force,
-        })?,
+        })
+        .map(|_| ())?,
 
         HookExecType::Pull => utils::pull(utils::pull::PullOptions {
             config: &context.config,
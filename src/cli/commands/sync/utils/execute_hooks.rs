@@ -1,35 +1,136 @@
 use crate::{
     config::prelude::{AppConfig, Hook, HookConfig},
-    hooks::prelude::HookContext,
-    log_debug,
+    hooks::{
+        artifacts::Artifacts,
+        condition::{Condition, ConditionContext, validate_when},
+        prelude::{CommandHook, HookContext},
+    },
+    log_debug, log_info,
+    utils::{
+        directories::directories,
+        history::{HISTORY_FILE, History},
+    },
 };
+use anyhow::Context;
 
-/// Executes a sequence of hooks over a given hook context, returning the final context.
+/// Evaluates the `when` condition of a hook against the context and the sync history.
+///
+/// # Returns
+/// `None` for hooks without a condition.
+pub fn evaluate_when(hook: &HookConfig, context: &HookContext) -> anyhow::Result<Option<bool>> {
+    let Some(when) = hook.when() else {
+        return Ok(None);
+    };
+
+    validate_when(hook)?;
+
+    let condition: Condition = when
+        .parse()
+        .with_context(|| format!("invalid condition for {}: {}", hook.hook_type(), when))?;
+
+    condition
+        .evaluate(&ConditionContext::new(
+            context,
+            *hook.exec_type(),
+            History::new(&directories().data_dir.join(HISTORY_FILE)),
+            chrono::Local::now(),
+        ))
+        .with_context(|| format!("failed to evaluate condition: {}", when))
+        .map(Some)
+}
+
+/// Whether a hook runs, hooks without a `when` condition always do.
+pub fn should_run(hook: &HookConfig, context: &HookContext) -> anyhow::Result<bool> {
+    let run = evaluate_when(hook, context)?.unwrap_or(true);
+
+    if !run {
+        log_info!(
+            "skipping {} hook, condition not met: {}",
+            hook.hook_type(),
+            hook.when().unwrap_or_default()
+        );
+    }
+
+    Ok(run)
+}
+
+/// Outcome of `execute_hooks`, removing the artifacts of the hooks when dropped.
+///
+/// Keep it alive until the processed content has been uploaded or moved in place.
+#[derive(Debug)]
+pub struct HookRun {
+    pub context: HookContext,
+    /// Hooks that ran, the ones whose `when` condition was not met are left out.
+    pub applied: Vec<HookConfig>,
+    pub artifacts: Artifacts,
+}
+
+/// Executes a sequence of hooks over a given hook context.
+///
+/// Every hook producing new content tracks its artifacts in the run, they are
+/// removed when it is dropped, or right away when a hook fails.
 ///
 /// # Parameters
 /// - `context`: The initial `HookContext` to process.
 /// - `hooks`: Slice of `HookConfig` representing the hooks to apply.
 ///
 /// # Returns
-/// An `anyhow::Result<HookContext>` containing the processed context after all hooks have been applied.
+/// The `HookRun` holding the processed context after all hooks have been applied.
 ///
 /// # Example
 /// ```rust, ignore
 /// let context = HookContext::new(some_path);
 /// let hooks = vec![HookConfig::Zip(/* ... */)];
-/// let result = execute_hooks(context, &hooks)?;
+/// let run = execute_hooks(context, &hooks, &config)?;
 /// ``
 pub fn execute_hooks(
-    mut context: HookContext,
+    context: HookContext,
     hooks: &[HookConfig],
     config: &AppConfig,
-) -> anyhow::Result<HookContext> {
+) -> anyhow::Result<HookRun> {
     log_debug!("executing hooks: {:?}", hooks);
 
+    let source = context.path.clone();
+    let mut run = HookRun {
+        context,
+        applied: Vec::new(),
+        artifacts: Artifacts::default(),
+    };
+
+    for hook in hooks {
+        if !should_run(hook, &run.context)? {
+            continue;
+        }
+
+        let processor: Box<dyn Hook> = Box::from(hook.clone());
+        let input = run.context.path.clone();
+
+        run.context = processor.process(run.context.clone(), config)?;
+        run.applied.push(hook.clone());
+
+        // hooks passing the content through created nothing, the source is never removed
+        if run.context.path != input && !source.starts_with(&run.context.path) {
+            processor.track_artifacts(&run.context, &mut run.artifacts, config);
+        }
+    }
+
+    Ok(run)
+}
+
+/// Runs the Command hooks deferred until the sync finished (`after_sync`).
+///
+/// # Parameters
+/// - `context`: Context pointing to `local_path`, with the synced content hash.
+/// - `hooks`: Hooks declared for the direction of the sync.
+pub fn execute_post_sync_hooks(context: &HookContext, hooks: &[HookConfig]) -> anyhow::Result<()> {
     for hook in hooks {
-        let hook: Box<dyn Hook> = Box::from(hook.clone());
-        context = hook.process(context, config)?;
+        if let HookConfig::Command(cfg) = hook
+            && cfg.after_sync
+            && should_run(hook, context)?
+        {
+            CommandHook::from(cfg.clone()).run(context)?;
+        }
     }
 
-    Ok(context)
+    Ok(())
 }
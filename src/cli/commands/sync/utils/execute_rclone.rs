@@ -1,12 +1,42 @@
+use crate::config::prelude::SyncMode;
 use anyhow::Context;
 
+/// Arguments of the rclone transfer run by `execute_rclone`.
+pub fn rclone_command_args(
+    mode: &SyncMode,
+    source_path: &str,
+    target_path: &str,
+    args: &[String],
+) -> Vec<String> {
+    let mut cmd_args: Vec<String> = [
+        mode.rclone_command(),
+        source_path,
+        target_path,
+        "--progress",
+        "--checksum",
+        "--transfers=8",
+        "--checkers=16",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+
+    if *mode == SyncMode::Mirror {
+        cmd_args.push(String::from("--delete-during"));
+    }
+
+    cmd_args.extend(args.iter().cloned());
+    cmd_args
+}
+
 /// Executes an rclone command with the provided arguments.
 ///
 /// # Parameters
 /// - `rclone_path`: Path to the rclone executable.
-/// - `source_path`: Source path for the copy operation.
-/// - `target_path`: Target path for the copy operation.
-/// - `args`: Optional slice of additional arguments to pass to rclone.
+/// - `mode`: Sync mode, selects the rclone subcommand (`copy`, `sync` or `move`).
+/// - `source_path`: Source path for the transfer.
+/// - `target_path`: Target path for the transfer.
+/// - `args`: Additional arguments to pass to rclone (see `AppConfig::rclone_args`).
 ///
 /// # Returns
 /// An `anyhow::Result<std::process::ExitStatus>` representing the result of the rclone process execution.
@@ -15,34 +45,21 @@ use anyhow::Context;
 /// ```rust, ignore
 /// let status = execute_rclone(
 ///     "rclone",
+///     &SyncMode::Copy,
 ///     "/path/to/source",
 ///     "remote:path",
-///     Some(&["--dry-run"]),
+///     &[String::from("--dry-run")],
 /// )?;
 /// ``
 pub fn execute_rclone(
     rclone_path: &str,
+    mode: &SyncMode,
     source_path: &str,
     target_path: &str,
-    args: Option<&[&str]>,
+    args: &[String],
 ) -> anyhow::Result<std::process::ExitStatus> {
-    let mut cmd_args = vec![
-        "copy",
-        source_path,
-        target_path,
-        "--progress",
-        "--checksum",
-        "--delete-during",
-        "--transfers=8",
-        "--checkers=16",
-    ];
-
-    if let Some(extra) = args {
-        cmd_args.extend_from_slice(extra);
-    }
-
     std::process::Command::new(rclone_path)
-        .args(cmd_args)
+        .args(rclone_command_args(mode, source_path, target_path, args))
         .status()
         .context("failed to execute rclone")
 }
@@ -0,0 +1,74 @@
+use crate::{
+    config::prelude::{HookExecType, PathConfig, Remote},
+    log_warn,
+    utils::{
+        history::{HISTORY_FILE, History, HistoryEntry, SyncStatus},
+        prelude::directories,
+    },
+};
+
+/// Result of a push or pull that completed without error.
+#[derive(Debug, Clone)]
+pub struct SyncOutcome {
+    pub status: SyncStatus,
+    pub hash: Option<String>,
+}
+
+impl SyncOutcome {
+    pub fn success(hash: String) -> Self {
+        Self {
+            status: SyncStatus::Success,
+            hash: Some(hash),
+        }
+    }
+
+    pub fn unchanged(hash: String) -> Self {
+        Self {
+            status: SyncStatus::Unchanged,
+            hash: Some(hash),
+        }
+    }
+
+    pub fn aborted() -> Self {
+        Self {
+            status: SyncStatus::Aborted,
+            hash: None,
+        }
+    }
+}
+
+/// Appends the outcome of a sync to the history. Failing to record is only a warning.
+pub fn record_history(
+    direction: HookExecType,
+    path_config: &PathConfig,
+    remote: &Remote,
+    result: &anyhow::Result<SyncOutcome>,
+    started: std::time::Instant,
+) {
+    let data_dir = &directories().data_dir;
+
+    if data_dir.as_os_str().is_empty() {
+        return;
+    }
+
+    let (status, hash, error) = match result {
+        Ok(outcome) => (outcome.status, outcome.hash.clone(), None),
+        Err(e) => (SyncStatus::Failed, None, Some(format!("{:#}", e))),
+    };
+
+    let entry = HistoryEntry {
+        timestamp: chrono::Utc::now().timestamp(),
+        path_id: path_config.id.clone(),
+        local_path: path_config.local_path.clone(),
+        remote: format!("{}:{}", remote.remote_name, path_config.remote_path),
+        direction,
+        status,
+        hash,
+        error,
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+
+    if let Err(e) = History::new(&data_dir.join(HISTORY_FILE)).append(&entry) {
+        log_warn!("failed to record sync history: {}", e);
+    }
+}
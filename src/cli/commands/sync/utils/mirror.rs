@@ -0,0 +1,158 @@
+use crate::{
+    config::prelude::{HookConfig, PathConfig, Remote},
+    hooks::backup::BackupType,
+    log_debug, log_info, log_warn,
+    utils::filter::PathFilter,
+};
+use anyhow::Context;
+use std::{
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
+
+const DRY_RUN_DELETE_MARKER: &str = "Skipped delete as --dry-run is set";
+
+pub struct MirrorGuardOptions<'a> {
+    pub rclone: &'a str,
+    pub source: &'a str,
+    pub target: &'a str,
+    pub args: &'a [String],
+    pub max_delete: u32,
+    pub backup_dir: Option<String>,
+}
+
+/// Resolves the `--backup-dir` for a mirror push from the first remote Backup hook.
+///
+/// # Parameters
+/// - `hooks`: Push hooks declared for the path.
+/// - `remote`: Remote the path is synced to.
+///
+/// # Returns
+/// `Some("<remote>:<backup_path>/mirror-<timestamp>")` when a remote backup is configured.
+pub fn remote_backup_dir(hooks: &[HookConfig], remote: &Remote) -> Option<String> {
+    hooks.iter().find_map(|hook| match hook {
+        HookConfig::Backup(cfg) if cfg.types.contains(&BackupType::Remote) => {
+            cfg.remote_path.as_ref().map(|path| {
+                format!(
+                    "{}:{}/mirror-{}",
+                    remote.remote_name,
+                    path.trim_end_matches('/'),
+                    chrono::Utc::now().timestamp()
+                )
+            })
+        }
+        _ => None,
+    })
+}
+
+/// Counts how many files a `rclone sync` would delete on the target by running a dry-run.
+pub fn count_remote_deletions(options: &MirrorGuardOptions) -> anyhow::Result<usize> {
+    let output = std::process::Command::new(options.rclone)
+        .args(["sync", options.source, options.target, "--dry-run"])
+        .args(options.args)
+        .output()
+        .context("failed to execute rclone dry-run")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "rclone dry-run failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter(|line| line.contains(DRY_RUN_DELETE_MARKER))
+        .count())
+}
+
+/// Asks the user whether to continue when a mirror exceeds the deletion threshold.
+///
+/// Without a terminal (e.g. `watch` or `schedule run` as a service) the threshold
+/// is a hard limit and the mirror is aborted.
+///
+/// # Returns
+/// - `Ok(true)` if the number of deletions is within the threshold or the user confirmed.
+/// - `Ok(false)` if the user declined or could not be asked.
+pub fn confirm_deletions(deletions: usize, max_delete: u32) -> anyhow::Result<bool> {
+    if deletions <= max_delete as usize {
+        return Ok(true);
+    }
+
+    log_warn!(
+        "mirror would delete {} file(s), threshold is {}",
+        deletions,
+        max_delete
+    );
+
+    if !std::io::stdin().is_terminal() {
+        log_warn!("no terminal to confirm the deletions, skipping mirror");
+        return Ok(false);
+    }
+
+    inquire::Confirm::new("Proceed with deletions?")
+        .with_default(false)
+        .prompt()
+        .context("failed to get confirmation")
+}
+
+/// Builds the safeguard arguments for a mirror push (`--max-delete` and `--backup-dir`).
+///
+/// # Returns
+/// - `Ok(Some(args))` with the arguments to append to the rclone call.
+/// - `Ok(None)` if the user aborted the mirror.
+pub fn mirror_guard(options: MirrorGuardOptions) -> anyhow::Result<Option<Vec<String>>> {
+    let deletions =
+        count_remote_deletions(&options).context("failed to preview mirror deletions")?;
+
+    log_info!("mirror will delete {} file(s) on target", deletions);
+
+    if !confirm_deletions(deletions, options.max_delete)? {
+        return Ok(None);
+    }
+
+    let mut args = vec![format!(
+        "--max-delete={}",
+        deletions.max(options.max_delete as usize)
+    )];
+
+    if let Some(backup_dir) = options.backup_dir {
+        log_info!("deleted/overwritten files will be moved to: {}", backup_dir);
+        args.push(format!("--backup-dir={}", backup_dir));
+    }
+
+    log_debug!("mirror args: {:?}", args);
+
+    Ok(Some(args))
+}
+
+/// Lists files inside `target` that are not present in `source` (relative paths).
+///
+/// Only files the filter of `path_config` includes are considered, files kept out
+/// of the sync (e.g. by `exclude` or `.rcloudignore`) are never stale.
+pub fn stale_local_files(
+    path_config: &PathConfig,
+    source: &Path,
+    target: &Path,
+) -> anyhow::Result<Vec<PathBuf>> {
+    if !source.is_dir() || !target.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let filter =
+        PathFilter::for_root(path_config, target).context("failed to build path filter")?;
+    let mut stale = Vec::new();
+
+    for entry in filter.walk(target) {
+        let relative_path = entry
+            .path()
+            .strip_prefix(target)
+            .context("failed to build relative path")?;
+
+        if !source.join(relative_path).exists() {
+            stale.push(relative_path.to_path_buf());
+        }
+    }
+
+    Ok(stale)
+}
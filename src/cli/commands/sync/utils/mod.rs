@@ -1,13 +1,20 @@
-pub mod compute_remote_filename;
 pub mod execute_hooks;
 pub mod execute_rclone;
+pub mod history;
+pub mod mirror;
 pub mod options;
 pub mod pull;
 pub mod push;
 
-pub use super::utils::compute_remote_filename::compute_remote_filename;
-pub use super::utils::execute_hooks::execute_hooks;
-pub use super::utils::execute_rclone::execute_rclone;
-pub use super::utils::options::{ForceResult, clean, force};
+pub use super::utils::execute_hooks::{
+    HookRun, evaluate_when, execute_hooks, execute_post_sync_hooks,
+};
+pub use super::utils::execute_rclone::{execute_rclone, rclone_command_args};
+pub use super::utils::history::{SyncOutcome, record_history};
+pub use super::utils::mirror::{MirrorGuardOptions, mirror_guard};
+pub use super::utils::options::{
+    ForceResult, clean, comparable_hash, force, migrate_hash, store_hash, stored_format_hash,
+};
 pub use super::utils::pull::pull;
-pub use super::utils::push::push;
+pub use super::utils::push::{push, push_path};
+pub use crate::config::remote_filename::{compute_remote_filename, is_multipart};
@@ -1,6 +1,10 @@
 use crate::{
-    config::prelude::{HookExecType, PathConfig},
+    config::prelude::{HookExecType, PathConfig, Registry},
     log_info,
+    utils::{
+        filter::PathFilter,
+        hash::{HASH_VERSION, Hash, HashAlgorithm, HashOptions},
+    },
 };
 use anyhow::{Context, Ok};
 
@@ -97,3 +101,106 @@ pub fn force(
         }
     }
 }
+
+/// Whether the hash stored in `path_config` uses the current format and algorithm.
+fn is_current_hash(path_config: &PathConfig, options: &HashOptions) -> bool {
+    path_config.hash_version == Some(HASH_VERSION)
+        && path_config.hash_algorithm.unwrap_or_default() == options.algorithm
+}
+
+/// Computes the hash of `path` in the same format as the hash stored in `path_config`.
+///
+/// Hashes stored before `HASH_VERSION` was introduced are treated as version 1, and
+/// hashes without a recorded algorithm as SHA-256, so they can be compared against
+/// the content instead of silently mismatching.
+///
+/// # Returns
+/// - `processed_hash` when the stored hash already uses the current format (or there is none).
+/// - The hash recomputed with the stored format and algorithm otherwise.
+pub fn comparable_hash(
+    path_config: &PathConfig,
+    path: &std::path::Path,
+    filter: &PathFilter,
+    options: &HashOptions,
+    processed_hash: &str,
+) -> anyhow::Result<String> {
+    if path_config.hash.is_none() || is_current_hash(path_config, options) {
+        return Ok(processed_hash.to_string());
+    }
+
+    let version = path_config.hash_version.unwrap_or(1);
+    let algorithm = path_config.hash_algorithm.unwrap_or_default();
+
+    log_info!(
+        "stored hash uses format v{} ({}), recomputing it that way to compare",
+        version,
+        algorithm
+    );
+
+    let stored_options = HashOptions {
+        algorithm,
+        ..options.clone()
+    };
+
+    Hash::hash_path_versioned(path, filter, &stored_options, version)
+        .with_context(|| format!("failed to calculate v{} {} hash", version, algorithm))
+}
+
+/// Hashes `path` with the filter of `path_config`, in the format of its stored hash.
+pub fn stored_format_hash(
+    path_config: &PathConfig,
+    path: &std::path::Path,
+    options: &HashOptions,
+) -> anyhow::Result<String> {
+    let filter = match path.is_dir() {
+        true => PathFilter::for_root(path_config, path),
+        false => PathFilter::from_path_config(path_config),
+    }
+    .context("failed to build path filter")?;
+
+    let hash = Hash::hash_path(path, &filter, options).context("failed to calculate hash")?;
+
+    comparable_hash(path_config, path, &filter, options, &hash)
+}
+
+/// Stores the hash of a path in the registry along with its format and algorithm.
+pub fn store_hash(
+    registry: &std::sync::Arc<std::sync::Mutex<Registry>>,
+    path_id: &str,
+    hash: String,
+    algorithm: HashAlgorithm,
+) -> anyhow::Result<()> {
+    registry
+        .lock()
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .tx(|rgx| {
+            if let Some(path) = rgx.paths.iter_mut().find(|p| p.id == path_id) {
+                path.hash = Some(hash);
+                path.hash_version = Some(HASH_VERSION);
+                path.hash_algorithm = Some(algorithm);
+            }
+        })
+        .context("failed to execute transaction")
+}
+
+/// Upgrades a stored hash to the current format once the content was verified unchanged.
+pub fn migrate_hash(
+    registry: &std::sync::Arc<std::sync::Mutex<Registry>>,
+    path_config: &PathConfig,
+    options: &HashOptions,
+    processed_hash: String,
+) -> anyhow::Result<()> {
+    if is_current_hash(path_config, options) {
+        return Ok(());
+    }
+
+    store_hash(registry, &path_config.id, processed_hash, options.algorithm)?;
+
+    log_info!(
+        "migrated stored hash to format v{} ({})",
+        HASH_VERSION,
+        options.algorithm
+    );
+
+    Ok(())
+}
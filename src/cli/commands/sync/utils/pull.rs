@@ -1,12 +1,18 @@
 use crate::{
     cli::commands::sync::utils,
     config::{
-        prelude::{AppConfig, HookConfig, HookExecType, PathConfig, Registry},
+        prelude::{AppConfig, HookConfig, HookExecType, PathConfig, Registry, SyncMode},
         remote::Remote,
     },
-    hooks::prelude::{HookContext, HookContextMetadata},
+    hooks::{
+        artifacts::{self, ArtifactDir, DOWNLOAD_PREFIX},
+        prelude::{HookContext, HookContextMetadata},
+    },
     log_debug, log_info, log_success, log_warn,
-    utils::hash,
+    utils::{
+        filter::PathFilter,
+        hash::{Hash, HashOptions},
+    },
 };
 use anyhow::Context;
 
@@ -26,12 +32,73 @@ pub struct PullOptions<'a> {
 }
 
 pub fn pull(options: PullOptions) -> anyhow::Result<()> {
-    let temp_dir = tempfile::tempdir().context("failed to create temp directory")?;
+    let started = std::time::Instant::now();
+    let result = run_pull(&options);
+
+    utils::record_history(
+        HookExecType::Pull,
+        options.paths.path_config,
+        options.paths.remote,
+        &result,
+        started,
+    );
+
+    result.map(|_| ())
+}
+
+/// Remote content of a path downloaded into a temp dir, with the pull hooks applied.
+///
+/// The download and the hook artifacts are removed when it is dropped.
+pub struct Download {
+    pub run: utils::HookRun,
+    remote_path: String,
+    rclone_args: Vec<String>,
+    _temp_dir: ArtifactDir,
+}
 
-    let remote_filename = match options.hooks.iter().any(|h| h.modifies_filename()) {
+impl Download {
+    /// Deletes the downloaded remote content, completing a `Move` pull.
+    ///
+    /// Only called once the content is in place, so a failed pull never loses it.
+    pub fn remove_remote(&self, rclone: &str) -> anyhow::Result<()> {
+        let output = std::process::Command::new(rclone)
+            .args(["delete", &self.remote_path])
+            .args(&self.rclone_args)
+            .output()
+            .context("failed to execute rclone delete")?;
+
+        anyhow::ensure!(
+            output.status.success(),
+            "failed to remove {}: {}",
+            self.remote_path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+
+        Ok(())
+    }
+}
+
+/// Downloads the remote content of a path and runs `hooks` over it, in reverse order.
+///
+/// # Parameters
+/// - `paths`: The rclone executable, remote and path to download.
+/// - `config`: Application config (rclone args, temp path).
+/// - `hooks`: Pull hooks of the path, in their configured order.
+///
+/// The remote content is always copied, `Move` pulls call `Download::remove_remote`
+/// once the content is in place.
+pub fn download(
+    paths: &PullOptionsPaths,
+    config: &AppConfig,
+    hooks: &[HookConfig],
+) -> anyhow::Result<Download> {
+    let temp_dir = artifacts::create_dir(DOWNLOAD_PREFIX, config)
+        .context("failed to create temp directory")?;
+
+    let remote_filename = match hooks.iter().any(|h| h.modifies_filename()) {
         true => Some(utils::compute_remote_filename(
-            options.hooks,
-            std::path::Path::new(&options.paths.path_config.remote_path)
+            hooks,
+            std::path::Path::new(&paths.path_config.remote_path)
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("archive"),
@@ -42,26 +109,45 @@ pub fn pull(options: PullOptions) -> anyhow::Result<()> {
     let remote_path = match &remote_filename {
         None => format!(
             "{}:{}",
-            options.paths.remote.remote_name, options.paths.path_config.remote_path
+            paths.remote.remote_name, paths.path_config.remote_path
         ),
         Some(filename) => {
             format!(
                 "{}:{}/{}",
-                options.paths.remote.remote_name, options.paths.path_config.remote_path, filename
+                paths.remote.remote_name, paths.path_config.remote_path, filename
             )
         }
     };
 
     log_debug!("remote_path: {:?}", remote_path);
 
+    let filter =
+        PathFilter::from_path_config(paths.path_config).context("failed to build path filter")?;
+
+    let mut rclone_args = config
+        .rclone_args(paths.remote, paths.path_config)
+        .context("failed to resolve rclone args")?;
+
+    if remote_filename.is_none() {
+        rclone_args.extend(filter.rclone_args());
+    }
+
+    log_debug!("rclone_args: {:?}", rclone_args);
+
+    // the parts of a multi-part artifact are downloaded into a directory named after it
+    let download_dir = match (&remote_filename, utils::is_multipart(hooks)) {
+        (Some(filename), true) => temp_dir.path().join(filename),
+        _ => temp_dir.path().to_path_buf(),
+    };
+
     let status = utils::execute_rclone(
-        options.paths.rclone,
+        paths.rclone,
+        &SyncMode::Copy,
         &remote_path,
-        temp_dir
-            .path()
+        download_dir
             .to_str()
-            .context("failed to convert tempdir path to str")?,
-        None,
+            .context("failed to convert download path to str")?,
+        &rclone_args,
     )?;
 
     if !status.success() {
@@ -91,47 +177,131 @@ pub fn pull(options: PullOptions) -> anyhow::Result<()> {
         downloaded_file.exists()
     );
 
-    let reversed_hooks: Vec<HookConfig> = options.hooks.iter().rev().cloned().collect();
-    let context = utils::execute_hooks(
+    let reversed_hooks: Vec<HookConfig> = hooks.iter().rev().cloned().collect();
+    let run = utils::execute_hooks(
         HookContext::new(
             downloaded_file,
-            options.paths.rclone,
-            options.paths.remote,
-            options.paths.path_config,
+            paths.rclone,
+            paths.remote,
+            paths.path_config,
         )
         .with_metadata(
             HookContextMetadata::SourceLocalPath,
-            &options.paths.path_config.local_path,
+            &paths.path_config.local_path,
         )
         .with_metadata(
             HookContextMetadata::SourceRemotePath,
-            &options.paths.path_config.remote_path,
+            &paths.path_config.remote_path,
         ),
         &reversed_hooks,
-        options.config,
+        config,
     )?;
 
-    let processed_hash = hash::Hash::hash_path(&context.path)
+    Ok(Download {
+        run,
+        remote_path,
+        rclone_args,
+        _temp_dir: temp_dir,
+    })
+}
+
+/// Removes the remote content of a `Move` pull, the local copy is already in place.
+fn remove_moved(download: &Download, rclone: &str) {
+    match download.remove_remote(rclone) {
+        Ok(()) => log_info!("removed pulled content from remote"),
+        Err(e) => log_warn!("pulled content kept on remote: {:#}", e),
+    }
+}
+
+fn run_pull(options: &PullOptions) -> anyhow::Result<utils::SyncOutcome> {
+    let is_move = options.paths.path_config.mode == SyncMode::Move;
+
+    if is_move {
+        log_warn!(
+            "using {} mode, remote files will be removed once pulled",
+            SyncMode::Move
+        );
+    }
+
+    let download = download(&options.paths, options.config, options.hooks)?;
+    let context = &download.run.context;
+
+    let filter = PathFilter::from_path_config(options.paths.path_config)
+        .context("failed to build path filter")?;
+
+    let processed_filter = match context.path.is_dir() {
+        true => PathFilter::for_root(options.paths.path_config, &context.path)
+            .context("failed to build path filter for processed content")?,
+        false => filter,
+    };
+
+    // downloaded content lives in a temp dir, caching its digests would be useless
+    let hash_options = HashOptions::from(options.config).without_cache();
+
+    let processed_hash = Hash::hash_path(&context.path, &processed_filter, &hash_options)
         .context("failed to calculate processed content hash")?;
 
     log_debug!("processed hash: {}", processed_hash);
 
+    let comparable_hash = utils::comparable_hash(
+        options.paths.path_config,
+        &context.path,
+        &processed_filter,
+        &hash_options,
+        &processed_hash,
+    )?;
+
     match utils::force(
         &HookExecType::Pull,
         options.force,
         options.paths.path_config,
-        &processed_hash,
+        &comparable_hash,
     ) {
         utils::ForceResult::Proceed => {}
         utils::ForceResult::HashMatch => {
+            utils::migrate_hash(
+                &options.registry,
+                options.paths.path_config,
+                &hash_options,
+                processed_hash.clone(),
+            )?;
             log_warn!("content unchanged (hash match). skipping");
-            return Ok(());
+
+            if is_move {
+                remove_moved(&download, options.paths.rclone);
+            }
+
+            return Ok(utils::SyncOutcome::unchanged(processed_hash));
         }
         utils::ForceResult::PathNotFound => {
             log_info!("local path does not exist, proceding with sync");
         }
     }
 
+    let stale_files = match options.paths.path_config.mode {
+        SyncMode::Mirror => {
+            let stale = utils::mirror::stale_local_files(
+                options.paths.path_config,
+                &context.path,
+                std::path::Path::new(&options.paths.path_config.local_path),
+            )
+            .context("failed to compute stale local files")?;
+
+            log_info!("mirror will delete {} local file(s)", stale.len());
+
+            if !utils::mirror::confirm_deletions(
+                stale.len(),
+                options.config.max_delete(options.paths.path_config),
+            )? {
+                log_warn!("mirror aborted by user");
+                return Ok(utils::SyncOutcome::aborted());
+            }
+
+            stale
+        }
+        SyncMode::Copy | SyncMode::Move => Vec::new(),
+    };
+
     log_info!("moving processed content to local_path");
 
     utils::clean(
@@ -187,20 +357,25 @@ pub fn pull(options: PullOptions) -> anyhow::Result<()> {
         std::fs::remove_dir_all(&context.path).context("failed to remove temp directory")?;
     }
 
-    options
-        .registry
-        .lock()
-        .map_err(|e| anyhow::anyhow!("{}", e))?
-        .tx(|rgx| {
-            if let Some(path) = rgx
-                .paths
-                .iter_mut()
-                .find(|p| p.id == options.paths.path_config.id)
-            {
-                path.hash = Some(processed_hash);
-            }
-        })
-        .context("failed to execute transaction")?;
+    for stale in &stale_files {
+        let stale_path = std::path::Path::new(&options.paths.path_config.local_path).join(stale);
+
+        log_debug!("removing stale file: {:?}", stale_path);
+
+        std::fs::remove_file(&stale_path)
+            .with_context(|| format!("failed to remove stale file: {:?}", stale_path))?;
+    }
+
+    utils::store_hash(
+        &options.registry,
+        &options.paths.path_config.id,
+        processed_hash.clone(),
+        hash_options.algorithm,
+    )?;
+
+    if is_move {
+        remove_moved(&download, options.paths.rclone);
+    }
 
     log_success!(
         "pulled from remote {}:{} -> {}",
@@ -209,5 +384,16 @@ pub fn pull(options: PullOptions) -> anyhow::Result<()> {
         options.paths.path_config.local_path
     );
 
-    Ok(())
+    utils::execute_post_sync_hooks(
+        &HookContext::new(
+            std::path::PathBuf::from(&options.paths.path_config.local_path),
+            options.paths.rclone,
+            options.paths.remote,
+            options.paths.path_config,
+        )
+        .with_metadata(HookContextMetadata::CalculatedHash, &processed_hash),
+        options.hooks,
+    )?;
+
+    Ok(utils::SyncOutcome::success(processed_hash))
 }
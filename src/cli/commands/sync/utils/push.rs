@@ -1,9 +1,14 @@
 use crate::{
-    cli::commands::sync::utils,
-    config::prelude::{AppConfig, HookConfig, HookExecType, PathConfig, Registry, Remote},
+    cli::{commands::sync::utils, context::CommandContext},
+    config::prelude::{
+        AppConfig, HookConfig, HookExecType, PathConfig, Registry, Remote, SyncMode,
+    },
     hooks::prelude::{HookContext, HookContextMetadata},
     log_debug, log_info, log_success, log_warn,
-    utils::hash,
+    utils::{
+        filter::PathFilter,
+        hash::{Hash, HashOptions},
+    },
 };
 use anyhow::Context;
 use std::path::PathBuf;
@@ -22,35 +27,73 @@ pub struct PushOptions<'a> {
     pub force: &'a bool,
 }
 
-pub fn push(options: PushOptions) -> anyhow::Result<()> {
+/// Pushes a path and records the outcome in the history.
+///
+/// # Returns
+/// The outcome, an aborted mirror is not an error but nothing was sent.
+pub fn push(options: PushOptions) -> anyhow::Result<utils::SyncOutcome> {
+    let started = std::time::Instant::now();
+    let result = run_push(&options);
+
+    utils::record_history(
+        HookExecType::Push,
+        options.paths.path_config,
+        options.paths.remote,
+        &result,
+        started,
+    );
+
+    result
+}
+
+fn run_push(options: &PushOptions) -> anyhow::Result<utils::SyncOutcome> {
     log_info!("running pre-transaction hooks");
 
-    let processed_hash = hash::Hash::hash_path(&std::path::PathBuf::from(
-        &options.paths.path_config.local_path,
-    ))
-    .context("failed to calculate content hash")?;
+    let filter = PathFilter::from_path_config(options.paths.path_config)
+        .context("failed to build path filter")?;
+
+    let local_path = PathBuf::from(&options.paths.path_config.local_path);
+    let hash_options = HashOptions::from(options.config);
+
+    let processed_hash = Hash::hash_path(&local_path, &filter, &hash_options)
+        .context("failed to calculate content hash")?;
 
     log_debug!("calculated hash: {}", processed_hash);
 
+    let comparable_hash = utils::comparable_hash(
+        options.paths.path_config,
+        &local_path,
+        &filter,
+        &hash_options,
+        &processed_hash,
+    )?;
+
     match utils::force(
         &HookExecType::Push,
         options.force,
         options.paths.path_config,
-        &processed_hash,
+        &comparable_hash,
     ) {
         utils::ForceResult::Proceed => {}
         utils::ForceResult::HashMatch => {
+            utils::migrate_hash(
+                &options.registry,
+                options.paths.path_config,
+                &hash_options,
+                processed_hash.clone(),
+            )?;
             log_warn!("content unchanged (hash match). skipping");
-            return Ok(());
+            return Ok(utils::SyncOutcome::unchanged(processed_hash));
         }
         utils::ForceResult::PathNotFound => {
             unreachable!();
         }
     }
 
-    let context = utils::execute_hooks(
+    // skipped hooks did not rename the content, the upload is named after the applied ones
+    let run = utils::execute_hooks(
         HookContext::new(
-            PathBuf::from(&options.paths.path_config.local_path),
+            local_path.clone(),
             options.paths.rclone,
             options.paths.remote,
             options.paths.path_config,
@@ -60,8 +103,11 @@ pub fn push(options: PushOptions) -> anyhow::Result<()> {
         options.config,
     )?;
 
+    let context = &run.context;
+    let applied = &run.applied;
+
     let final_name = utils::compute_remote_filename(
-        options.hooks,
+        applied,
         std::path::Path::new(&options.paths.path_config.remote_path)
             .file_name()
             .and_then(|n| n.to_str())
@@ -102,13 +148,10 @@ pub fn push(options: PushOptions) -> anyhow::Result<()> {
                 .context("failed to move file")?;
             }
 
+            // move_dir would nest the directory inside renamed_path, both share a parent
             if context.path.is_dir() {
-                fs_extra::dir::move_dir(
-                    &context.path,
-                    &renamed_path,
-                    &fs_extra::dir::CopyOptions::new().overwrite(true),
-                )
-                .context("failed to move directory")?;
+                std::fs::rename(&context.path, &renamed_path)
+                    .context("failed to move directory")?;
             }
 
             renamed_path
@@ -117,36 +160,77 @@ pub fn push(options: PushOptions) -> anyhow::Result<()> {
 
     log_debug!("final_path: {:?}", final_path);
 
-    let status = utils::execute_rclone(
-        options.paths.rclone,
-        final_path
-            .to_str()
-            .context("failed to convert final_path to str")?,
-        &format!(
-            "{}:{}",
-            options.paths.remote.remote_name, options.paths.path_config.remote_path
-        ),
-        None,
-    )?;
+    let mut rclone_args = options
+        .config
+        .rclone_args(options.paths.remote, options.paths.path_config)
+        .context("failed to resolve rclone args")?;
+
+    if !applied.iter().any(|h| h.modifies_filename()) {
+        rclone_args.extend(filter.rclone_args());
+    }
+
+    let source = final_path
+        .to_str()
+        .context("failed to convert final_path to str")?;
+
+    // parts are synced into their own directory, rclone would spread them in remote_path
+    let target = format!(
+        "{}:{}",
+        options.paths.remote.remote_name,
+        match utils::is_multipart(applied) {
+            true => options.paths.path_config.remote_object(applied),
+            false => options.paths.path_config.remote_path.clone(),
+        }
+    );
+
+    let mode = &options.paths.path_config.mode;
+
+    match mode {
+        SyncMode::Copy => {}
+        SyncMode::Mirror => {
+            let guard = utils::mirror_guard(utils::MirrorGuardOptions {
+                rclone: options.paths.rclone,
+                source,
+                target: &target,
+                args: &rclone_args,
+                max_delete: options.config.max_delete(options.paths.path_config),
+                backup_dir: utils::mirror::remote_backup_dir(
+                    &options.paths.path_config.hooks.push,
+                    options.paths.remote,
+                ),
+            })
+            .context("failed to prepare mirror")?;
+
+            match guard {
+                Some(args) => rclone_args.extend(args),
+                None => {
+                    log_warn!("mirror aborted by user");
+                    return Ok(utils::SyncOutcome::aborted());
+                }
+            }
+        }
+        SyncMode::Move => {
+            log_warn!(
+                "using {} mode, source files will be removed after upload",
+                mode
+            );
+        }
+    }
+
+    log_debug!("rclone_args: {:?}", rclone_args);
+
+    let status = utils::execute_rclone(options.paths.rclone, mode, source, &target, &rclone_args)?;
 
     if !status.success() {
         anyhow::bail!("rclone push sync failed");
     }
 
-    options
-        .registry
-        .lock()
-        .map_err(|e| anyhow::anyhow!("{}", e))?
-        .tx(|rgx| {
-            if let Some(path) = rgx
-                .paths
-                .iter_mut()
-                .find(|p| p.id == options.paths.path_config.id)
-            {
-                path.hash = Some(processed_hash);
-            }
-        })
-        .context("failed to execute transaction")?;
+    utils::store_hash(
+        &options.registry,
+        &options.paths.path_config.id,
+        processed_hash.clone(),
+        hash_options.algorithm,
+    )?;
 
     log_success!(
         "sent to remote {} -> {}:{}",
@@ -155,5 +239,53 @@ pub fn push(options: PushOptions) -> anyhow::Result<()> {
         options.paths.path_config.remote_path
     );
 
-    Ok(())
+    utils::execute_post_sync_hooks(
+        &HookContext::new(
+            PathBuf::from(&options.paths.path_config.local_path),
+            options.paths.rclone,
+            options.paths.remote,
+            options.paths.path_config,
+        )
+        .with_metadata(HookContextMetadata::CalculatedHash, &processed_hash),
+        options.hooks,
+    )?;
+
+    Ok(utils::SyncOutcome::success(processed_hash))
+}
+
+/// Pushes a registered path by ID without prompting, honoring the hash check.
+///
+/// Used by unattended runs (watcher, scheduler) that only know the path ID.
+/// They must check the outcome, a mirror needing a confirmation is aborted.
+pub fn push_path<L: Clone>(
+    context: &CommandContext<L>,
+    path_id: &str,
+) -> anyhow::Result<utils::SyncOutcome> {
+    let path_config = context
+        .with_registry()?
+        .paths
+        .iter()
+        .find(|p| p.id == path_id)
+        .ok_or_else(|| anyhow::anyhow!("path does not exists"))?
+        .clone();
+
+    let remote_config = context
+        .with_registry()?
+        .remotes
+        .iter()
+        .find(|r| r.id == path_config.remote_id)
+        .ok_or_else(|| anyhow::anyhow!("remote does not exists"))?
+        .clone();
+
+    push(PushOptions {
+        config: &context.config,
+        registry: std::sync::Arc::clone(&context.registry),
+        paths: PushOptionsPaths {
+            rclone: &context.global.rclone,
+            remote: &remote_config,
+            path_config: &path_config,
+        },
+        hooks: &path_config.hooks.push,
+        force: &false,
+    })
 }
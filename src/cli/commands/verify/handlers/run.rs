@@ -0,0 +1,233 @@
+use crate::{
+    cli::{
+        commands::sync::utils::{
+            self as sync_utils,
+            pull::{PullOptionsPaths, download},
+        },
+        context::CommandContext,
+    },
+    config::prelude::{AppConfig, HookConfig, PathConfig, Remote},
+    log_error, log_info, log_success, log_warn,
+    utils::{filter::PathFilter, hash::HashOptions},
+};
+use anyhow::Context;
+use std::path::Path;
+
+#[derive(Clone, Debug)]
+pub struct LocalArgs<'a> {
+    pub path_id: &'a Option<String>,
+    pub tags: &'a [String],
+    pub rclone_check: &'a bool,
+}
+
+/// How one side of a path compares with the hash stored in the registry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Match,
+    Drift,
+    Missing,
+}
+
+fn compare(path_config: &PathConfig, hash: &str) -> State {
+    match path_config.hash.as_deref() == Some(hash) {
+        true => State::Match,
+        false => State::Drift,
+    }
+}
+
+/// Compares the local tree with the hash of the last sync.
+fn local_state(config: &AppConfig, path_config: &PathConfig) -> anyhow::Result<State> {
+    let local_path = Path::new(&path_config.local_path);
+
+    if !local_path.exists() {
+        return Ok(State::Missing);
+    }
+
+    let hash = sync_utils::stored_format_hash(path_config, local_path, &HashOptions::from(config))?;
+
+    Ok(compare(path_config, &hash))
+}
+
+/// Downloads the remote content, reverses the hook chain and compares it with the registry.
+///
+/// Backup and Command hooks are left out, verifying must not have side effects.
+fn remote_state(
+    config: &AppConfig,
+    rclone: &str,
+    remote: &Remote,
+    path_config: &PathConfig,
+) -> anyhow::Result<State> {
+    let hooks: Vec<HookConfig> = path_config
+        .hooks
+        .pull
+        .iter()
+        .filter(|h| !matches!(h, HookConfig::Backup(_) | HookConfig::Command(_)))
+        .cloned()
+        .collect();
+
+    let download = download(
+        &PullOptionsPaths {
+            rclone,
+            remote,
+            path_config,
+        },
+        config,
+        &hooks,
+    )?;
+
+    let path = &download.run.context.path;
+
+    if !path.exists() {
+        return Ok(State::Missing);
+    }
+
+    // downloaded content lives in a temp dir, caching its digests would be useless
+    let hash = sync_utils::stored_format_hash(
+        path_config,
+        path,
+        &HashOptions::from(config).without_cache(),
+    )?;
+
+    Ok(compare(path_config, &hash))
+}
+
+/// Compares the local tree and the remote with `rclone check`, without downloading.
+fn rclone_check(
+    config: &AppConfig,
+    rclone: &str,
+    remote: &Remote,
+    path_config: &PathConfig,
+) -> anyhow::Result<State> {
+    let mut args = config
+        .rclone_args(remote, path_config)
+        .context("failed to resolve rclone args")?;
+
+    args.extend(
+        PathFilter::from_path_config(path_config)
+            .context("failed to build path filter")?
+            .rclone_args(),
+    );
+
+    let status = std::process::Command::new(rclone)
+        .arg("check")
+        .arg(&path_config.local_path)
+        .arg(format!(
+            "{}:{}",
+            remote.remote_name, path_config.remote_path
+        ))
+        .args(args)
+        .status()
+        .context("failed to execute rclone check")?;
+
+    Ok(match status.success() {
+        true => State::Match,
+        false => State::Drift,
+    })
+}
+
+/// Verifies one path, returning the drift found, empty when everything matches.
+fn verify_path(
+    context: &CommandContext<LocalArgs>,
+    path_config: &PathConfig,
+    remote: &Remote,
+) -> anyhow::Result<Vec<String>> {
+    let mut drift = Vec::new();
+    let hookless = path_config.hooks.push.is_empty() && path_config.hooks.pull.is_empty();
+    let local = local_state(&context.config, path_config)?;
+
+    if *context.local.rclone_check && hookless {
+        log_info!("checking {} with rclone check", path_config.id);
+
+        if local == State::Missing {
+            drift.push(String::from("local path is missing"));
+        } else if rclone_check(&context.config, &context.global.rclone, remote, path_config)?
+            == State::Drift
+        {
+            drift.push(String::from("remote differs from the local tree"));
+        }
+
+        if path_config.hash.is_some() && local == State::Drift {
+            drift.push(String::from("local tree changed since the last sync"));
+        }
+
+        return Ok(drift);
+    }
+
+    if path_config.hash.is_none() {
+        drift.push(String::from("no hash recorded, the path was never synced"));
+        return Ok(drift);
+    }
+
+    log_info!("downloading {} to verify it", path_config.id);
+
+    match remote_state(&context.config, &context.global.rclone, remote, path_config)? {
+        State::Match => {}
+        State::Drift => drift.push(String::from("remote differs from the registry")),
+        State::Missing => drift.push(String::from("remote content is missing")),
+    }
+
+    match local {
+        State::Match => {}
+        State::Drift => drift.push(String::from("local tree changed since the last sync")),
+        State::Missing => drift.push(String::from("local path is missing")),
+    }
+
+    Ok(drift)
+}
+
+pub fn verify_run(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    let paths: Vec<PathConfig> = context
+        .with_registry()?
+        .paths
+        .iter()
+        .filter(|p| context.local.path_id.as_ref().is_none_or(|id| &p.id == id))
+        .filter(|p| {
+            context.local.tags.is_empty() || p.tags.iter().any(|t| context.local.tags.contains(t))
+        })
+        .cloned()
+        .collect();
+
+    anyhow::ensure!(!paths.is_empty(), "no path to verify");
+
+    let mut failed = 0;
+
+    for path_config in &paths {
+        let remote = context
+            .with_registry()?
+            .remotes
+            .iter()
+            .find(|r| r.id == path_config.remote_id)
+            .cloned();
+
+        let result = match remote {
+            Some(remote) => verify_path(&context, path_config, &remote),
+            None => Err(anyhow::anyhow!("remote does not exists")),
+        };
+
+        match result {
+            Ok(drift) if drift.is_empty() => log_success!(
+                "{}: {} matches {}",
+                path_config.id,
+                path_config.local_path,
+                path_config.remote_path
+            ),
+            Ok(drift) => {
+                failed += 1;
+                log_warn!("{}: {}", path_config.id, drift.join(", "));
+            }
+            Err(e) => {
+                failed += 1;
+                log_error!("{}: failed to verify: {:#}", path_config.id, e);
+            }
+        }
+    }
+
+    anyhow::ensure!(
+        failed == 0,
+        "{} of {} path(s) failed verification",
+        failed,
+        paths.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand)]
+pub enum WatchCommand {
+    #[command(about = "Watch paths and push them when their content changes")]
+    Start {
+        #[arg(long = "path", value_name = "PATH_ID", help = "ID of a path to watch")]
+        path_ids: Vec<String>,
+
+        #[arg(
+            long,
+            value_name = "...TAGS",
+            value_delimiter = ',',
+            help = "comma separated tags to watch"
+        )]
+        tags: Vec<String>,
+
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            help = "Quiet period before pushing a path"
+        )]
+        debounce: Option<u64>,
+    },
+    #[command(about = "Show the state of the running watcher")]
+    Status,
+}
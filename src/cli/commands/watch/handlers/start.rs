@@ -0,0 +1,142 @@
+use crate::{
+    cli::{
+        commands::{
+            sync::utils as sync_utils,
+            watch::utils::{
+                Debouncer, WatchState, WatchStatus, WatchTarget, status::WATCH_STATUS_FILE,
+            },
+        },
+        context::CommandContext,
+    },
+    log_debug, log_error, log_info, log_success, log_warn,
+    utils::{history::SyncStatus, prelude::directories},
+};
+use anyhow::Context;
+use notify::Watcher;
+use std::{
+    sync::mpsc::RecvTimeoutError,
+    time::{Duration, Instant},
+};
+
+const TICK: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Debug)]
+pub struct LocalArgs<'a> {
+    pub path_ids: &'a [String],
+    pub tags: &'a [String],
+    pub debounce: &'a Option<u64>,
+}
+
+pub fn watch_start(context: CommandContext<LocalArgs>) -> anyhow::Result<()> {
+    let targets = context
+        .with_registry()?
+        .paths
+        .iter()
+        .filter(|p| {
+            (context.local.path_ids.is_empty() && context.local.tags.is_empty())
+                || context.local.path_ids.contains(&p.id)
+                || p.tags.iter().any(|t| context.local.tags.contains(t))
+        })
+        .map(|p| WatchTarget::new(p).map(|target| (target, p.local_path.clone())))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    if targets.is_empty() {
+        log_warn!("no paths to watch");
+        return Ok(());
+    }
+
+    let delay = Duration::from_secs(
+        context
+            .local
+            .debounce
+            .unwrap_or(context.config.core.watch_debounce_secs),
+    );
+
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    let mut watcher = notify::recommended_watcher(tx).context("failed to create file watcher")?;
+
+    for (target, local_path) in &targets {
+        let mode = match target.root.is_dir() {
+            true => notify::RecursiveMode::Recursive,
+            false => notify::RecursiveMode::NonRecursive,
+        };
+
+        watcher
+            .watch(target.watch_dir(), mode)
+            .with_context(|| format!("failed to watch path: {}", local_path))?;
+
+        log_info!("watching {}", local_path);
+    }
+
+    let mut status = WatchStatus::new(
+        directories().data_dir.join(WATCH_STATUS_FILE),
+        targets
+            .iter()
+            .map(|(target, local_path)| (target.path_id.clone(), local_path.clone()))
+            .collect(),
+    );
+
+    status.save().context("failed to write watch status")?;
+
+    let targets: Vec<WatchTarget> = targets.into_iter().map(|(target, _)| target).collect();
+    let mut debouncer = Debouncer::new(delay);
+
+    log_info!(
+        "watching {} path(s), pushing after {}s without changes",
+        targets.len(),
+        delay.as_secs()
+    );
+
+    loop {
+        match rx.recv_timeout(TICK) {
+            Ok(Ok(event)) => {
+                if matches!(event.kind, notify::EventKind::Access(_)) {
+                    continue;
+                }
+
+                for target in &targets {
+                    if !event.paths.iter().any(|p| target.matches(p)) {
+                        continue;
+                    }
+
+                    log_debug!("change detected for {}: {:?}", target.path_id, event.paths);
+
+                    if !debouncer.is_pending(&target.path_id) {
+                        status.update(&target.path_id, WatchState::Pending, None)?;
+                    }
+
+                    debouncer.touch(&target.path_id, Instant::now());
+                }
+            }
+            Ok(Err(e)) => log_warn!("watch error: {}", e),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                status.remove();
+                anyhow::bail!("file watcher stopped unexpectedly");
+            }
+        }
+
+        for path_id in debouncer.due(Instant::now()) {
+            status.update(&path_id, WatchState::Syncing, None)?;
+
+            match sync_utils::push_path(&context, &path_id) {
+                // a mirror over max_delete cannot be confirmed while watching
+                Ok(outcome) if outcome.status == SyncStatus::Aborted => {
+                    let error = String::from("mirror aborted, the deletions were not confirmed");
+
+                    status.update(&path_id, WatchState::Failed, Some(error.clone()))?;
+                    log_error!("watch push failed for {}: {}", path_id, error);
+                }
+                Ok(_) => {
+                    status.update(&path_id, WatchState::Idle, None)?;
+                    log_success!("watch push finished for {}", path_id);
+                }
+                Err(e) => {
+                    status.update(&path_id, WatchState::Failed, Some(e.to_string()))?;
+                    log_error!("watch push failed for {}: {}", path_id, e);
+                }
+            }
+        }
+    }
+}
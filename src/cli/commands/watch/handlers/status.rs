@@ -0,0 +1,76 @@
+use crate::{
+    cli::{
+        commands::watch::utils::{WatchState, WatchStatus, status::WATCH_STATUS_FILE},
+        context::CommandContext,
+    },
+    log_warn,
+    utils::prelude::directories,
+};
+use console::Style;
+
+fn format_timestamp(timestamp: Option<i64>) -> String {
+    timestamp
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|| String::from("-"))
+}
+
+/// Whether the watcher process is still alive, `None` when it cannot be determined.
+fn is_running(pid: u32) -> Option<bool> {
+    match cfg!(target_os = "linux") {
+        true => Some(std::path::Path::new(&format!("/proc/{}", pid)).exists()),
+        false => None,
+    }
+}
+
+pub fn watch_status(_context: CommandContext) -> anyhow::Result<()> {
+    let status_path = directories().data_dir.join(WATCH_STATUS_FILE);
+
+    if !status_path.exists() {
+        log_warn!("watcher is not running");
+        return Ok(());
+    }
+
+    let status = WatchStatus::load(&status_path)?;
+
+    if is_running(status.pid) == Some(false) {
+        log_warn!(
+            "watcher (pid {}) is not running, status may be stale",
+            status.pid
+        );
+    }
+
+    println!(
+        "watcher pid {} (started {}, updated {})",
+        status.pid,
+        format_timestamp(Some(status.started_at)),
+        format_timestamp(Some(status.updated_at))
+    );
+
+    for path in &status.paths {
+        let state_style = match path.state {
+            WatchState::Idle => Style::new().green(),
+            WatchState::Pending => Style::new().yellow(),
+            WatchState::Syncing => Style::new().cyan(),
+            WatchState::Failed => Style::new().red(),
+        };
+
+        println!(
+            "{} {} [last change: {}, last push: {}]",
+            state_style.apply_to(format!("{:>8}", path.state.to_string())),
+            path.local_path,
+            format_timestamp(path.last_change),
+            format_timestamp(path.last_push),
+        );
+
+        if let Some(error) = &path.last_error {
+            println!("         {}", Style::new().red().apply_to(error));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Tracks the last change of each path and releases it once it has been quiet for `delay`.
+#[derive(Debug)]
+pub struct Debouncer {
+    delay: Duration,
+    pending: HashMap<String, Instant>,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Records a change for `path_id`, restarting its quiet period.
+    pub fn touch(&mut self, path_id: &str, now: Instant) {
+        self.pending.insert(path_id.to_string(), now);
+    }
+
+    pub fn is_pending(&self, path_id: &str) -> bool {
+        self.pending.contains_key(path_id)
+    }
+
+    /// Removes and returns the paths whose quiet period elapsed.
+    pub fn due(&mut self, now: Instant) -> Vec<String> {
+        let mut due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, changed_at)| now.duration_since(**changed_at) >= self.delay)
+            .map(|(path_id, _)| path_id.clone())
+            .collect();
+
+        due.sort();
+
+        for path_id in &due {
+            self.pending.remove(path_id);
+        }
+
+        due
+    }
+}
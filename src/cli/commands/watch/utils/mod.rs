@@ -0,0 +1,7 @@
+pub mod debouncer;
+pub mod status;
+pub mod targets;
+
+pub use super::utils::debouncer::Debouncer;
+pub use super::utils::status::{WatchState, WatchStatus};
+pub use super::utils::targets::WatchTarget;
@@ -0,0 +1,127 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub const WATCH_STATUS_FILE: &str = "watch-status.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchState {
+    Idle,
+    Pending,
+    Syncing,
+    Failed,
+}
+
+impl std::fmt::Display for WatchState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WatchState::Idle => write!(f, "idle"),
+            WatchState::Pending => write!(f, "pending"),
+            WatchState::Syncing => write!(f, "syncing"),
+            WatchState::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchPathStatus {
+    pub path_id: String,
+    pub local_path: String,
+    pub state: WatchState,
+
+    #[serde(default)]
+    pub last_change: Option<i64>,
+
+    #[serde(default)]
+    pub last_push: Option<i64>,
+
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+/// Snapshot of a running watcher, written to the data directory on every change
+/// so `rcloud watch status` (or any other tool) can inspect it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchStatus {
+    pub pid: u32,
+    pub started_at: i64,
+    pub updated_at: i64,
+    pub paths: Vec<WatchPathStatus>,
+
+    #[serde(skip)]
+    status_path: PathBuf,
+}
+
+impl WatchStatus {
+    pub fn new(status_path: PathBuf, paths: Vec<(String, String)>) -> Self {
+        let now = chrono::Utc::now().timestamp();
+
+        Self {
+            pid: std::process::id(),
+            started_at: now,
+            updated_at: now,
+            paths: paths
+                .into_iter()
+                .map(|(path_id, local_path)| WatchPathStatus {
+                    path_id,
+                    local_path,
+                    state: WatchState::Idle,
+                    last_change: None,
+                    last_push: None,
+                    last_error: None,
+                })
+                .collect(),
+            status_path,
+        }
+    }
+
+    pub fn load(status_path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(status_path)
+            .with_context(|| format!("failed to read watch status: {:?}", status_path))?;
+
+        serde_json::from_str(&content).context("failed to parse watch status")
+    }
+
+    /// Updates the state of a path and persists the snapshot.
+    pub fn update(
+        &mut self,
+        path_id: &str,
+        state: WatchState,
+        error: Option<String>,
+    ) -> anyhow::Result<()> {
+        let now = chrono::Utc::now().timestamp();
+
+        if let Some(path) = self.paths.iter_mut().find(|p| p.path_id == path_id) {
+            match state {
+                WatchState::Pending => path.last_change = Some(now),
+                WatchState::Idle => path.last_push = Some(now),
+                _ => {}
+            }
+
+            path.state = state;
+            path.last_error = error;
+        }
+
+        self.save()
+    }
+
+    pub fn save(&mut self) -> anyhow::Result<()> {
+        self.updated_at = chrono::Utc::now().timestamp();
+
+        let contents =
+            serde_json::to_string_pretty(&self).context("failed to serialize watch status")?;
+
+        let temp_path = self.status_path.with_extension("json.tmp");
+
+        std::fs::write(&temp_path, contents)
+            .with_context(|| format!("failed to write watch status: {:?}", temp_path))?;
+
+        std::fs::rename(&temp_path, &self.status_path)
+            .with_context(|| format!("failed to replace watch status: {:?}", self.status_path))
+    }
+
+    pub fn remove(&self) {
+        let _ = std::fs::remove_file(&self.status_path);
+    }
+}
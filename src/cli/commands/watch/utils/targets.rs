@@ -0,0 +1,49 @@
+use crate::{config::prelude::PathConfig, utils::filter::PathFilter};
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+
+/// A path being watched, resolved to an absolute root.
+#[derive(Debug, Clone)]
+pub struct WatchTarget {
+    pub path_id: String,
+    pub root: PathBuf,
+    filter: PathFilter,
+}
+
+impl WatchTarget {
+    pub fn new(path_config: &PathConfig) -> anyhow::Result<Self> {
+        let root = std::fs::canonicalize(&path_config.local_path)
+            .with_context(|| format!("failed to resolve path: {}", path_config.local_path))?;
+
+        Ok(Self {
+            path_id: path_config.id.clone(),
+            filter: PathFilter::from_path_config(path_config)
+                .context("failed to build path filter")?,
+            root,
+        })
+    }
+
+    /// Directory registered with the watcher.
+    ///
+    /// Single files are watched through their parent, since editors usually
+    /// replace files on save and the original inode would stop reporting events.
+    pub fn watch_dir(&self) -> &Path {
+        match self.root.is_dir() {
+            true => &self.root,
+            false => self.root.parent().unwrap_or(&self.root),
+        }
+    }
+
+    /// Whether a change reported for `path` affects this target.
+    pub fn matches(&self, path: &Path) -> bool {
+        if !self.root.is_dir() {
+            return path == self.root;
+        }
+
+        match path.strip_prefix(&self.root) {
+            Ok(relative) if relative.as_os_str().is_empty() => true,
+            Ok(relative) => self.filter.is_included(relative, path.is_dir()),
+            Err(_) => false,
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::cli::commands::{
-    path::command::PathCommand, remote::command::RemoteCommand, sync::command::SyncCommand,
+    backup::command::BackupCommand, hooks::command::HooksCommand, path::command::PathCommand,
+    remote::command::RemoteCommand, schedule::command::ScheduleCommand, sync::command::SyncCommand,
+    watch::command::WatchCommand,
 };
 use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
@@ -93,6 +95,86 @@ pub enum Commands {
         #[command(subcommand)]
         action: SyncCommand,
     },
+    #[command(about = "Show which paths changed locally or on their remote since the last sync")]
+    Status {
+        #[arg(
+            long,
+            value_name = "...TAGS",
+            value_delimiter = ',',
+            help = "comma separated tags to show"
+        )]
+        tags: Vec<String>,
+
+        #[arg(long, help = "Only check local paths, without contacting the remotes")]
+        offline: bool,
+    },
+    #[command(about = "Check that the remotes hold what the registry recorded")]
+    Verify {
+        #[arg(long = "path", value_name = "PATH_ID", help = "Only verify this path")]
+        path_id: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "...TAGS",
+            value_delimiter = ',',
+            help = "comma separated tags to verify"
+        )]
+        tags: Vec<String>,
+
+        #[arg(
+            long,
+            help = "Use rclone check for paths without hooks instead of downloading them"
+        )]
+        rclone_check: bool,
+    },
+    #[command(about = "Backup Hook replicas")]
+    Backup {
+        #[command(subcommand)]
+        action: BackupCommand,
+    },
+    #[command(about = "Inspect the hooks of a path")]
+    Hooks {
+        #[command(subcommand)]
+        action: HooksCommand,
+    },
+    #[command(about = "Show the sync history")]
+    History {
+        #[arg(long = "path", value_name = "PATH_ID", help = "Only show this path")]
+        path_id: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "WHEN",
+            help = "Only show entries since a duration (7d), a date (YYYY-MM-DD) or RFC 3339"
+        )]
+        since: Option<String>,
+
+        #[arg(long, default_value_t = 50, help = "Maximum number of entries")]
+        limit: usize,
+    },
+    #[command(about = "Remove temp artifacts left behind by interrupted syncs")]
+    Gc {
+        #[arg(
+            long,
+            value_name = "AGE",
+            default_value = "1h",
+            help = "Only remove artifacts untouched for this long (30m, 1h, 2d)"
+        )]
+        older_than: String,
+
+        #[arg(long, help = "Only show the artifacts that would be removed")]
+        dry_run: bool,
+    },
+    #[command(about = "Scheduled syncs")]
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleCommand,
+    },
+    #[command(about = "Watch paths and push them on change")]
+    Watch {
+        #[command(subcommand)]
+        action: WatchCommand,
+    },
     #[command(about = "Configure CLI")]
     Configure,
     #[command(about = "Generate completions for your current shell")]
@@ -17,9 +17,17 @@ use_handlers! {
     simple: {
         (remote, list),
         (path, list),
-        (configure, setup)
+        (configure, setup),
+        (schedule, list),
+        (watch, status)
     },
     with_args: {
+        (backup, list),
+        (backup, prune),
+        (backup, restore),
+        (history, list),
+        (hooks, explain),
+        (gc, run),
         (remote, add),
         (remote, remove),
         (remote, update),
@@ -27,7 +35,12 @@ use_handlers! {
         (path, add),
         (path, remove),
         (sync, single),
+        (status, run),
         (sync, all),
+        (verify, run),
+        (schedule, run),
+        (schedule, install),
+        (watch, start),
     }
 }
 
@@ -103,11 +116,15 @@ pub fn run() -> anyhow::Result<(), anyhow::Error> {
                     remote_id,
                     local_path,
                     remote_path,
+                    mode,
+                    schedule,
                 } => {
                     path_add(context.with_args(PathAddArgs {
                         remote_id,
                         local_path,
                         remote_path,
+                        mode,
+                        schedule,
                     }))?;
                 }
 
@@ -136,6 +153,104 @@ pub fn run() -> anyhow::Result<(), anyhow::Error> {
                 }
             },
 
+            Commands::Status { tags, offline } => {
+                status_run(context.with_args(StatusRunArgs { tags, offline }))?;
+            }
+
+            Commands::Verify {
+                path_id,
+                tags,
+                rclone_check,
+            } => {
+                verify_run(context.with_args(VerifyRunArgs {
+                    path_id,
+                    tags,
+                    rclone_check,
+                }))?;
+            }
+
+            Commands::Backup { action } => match action {
+                commands::backup::command::BackupCommand::List { path_id } => {
+                    backup_list(context.with_args(BackupListArgs { path_id }))?;
+                }
+
+                commands::backup::command::BackupCommand::Prune { path_id, dry_run } => {
+                    backup_prune(context.with_args(BackupPruneArgs { path_id, dry_run }))?;
+                }
+
+                commands::backup::command::BackupCommand::Restore {
+                    path_id,
+                    replica,
+                    at,
+                    target,
+                    yes,
+                } => {
+                    backup_restore(context.with_args(BackupRestoreArgs {
+                        path_id,
+                        replica,
+                        at,
+                        target,
+                        yes,
+                    }))?;
+                }
+            },
+
+            Commands::Hooks { action } => match action {
+                commands::hooks::command::HooksCommand::Explain { path_id, direction } => {
+                    hooks_explain(context.with_args(HooksExplainArgs { path_id, direction }))?;
+                }
+            },
+
+            Commands::History {
+                path_id,
+                since,
+                limit,
+            } => {
+                history_list(context.with_args(HistoryListArgs {
+                    path_id,
+                    since,
+                    limit,
+                }))?;
+            }
+
+            Commands::Gc {
+                older_than,
+                dry_run,
+            } => {
+                gc_run(context.with_args(GcRunArgs {
+                    older_than,
+                    dry_run,
+                }))?;
+            }
+
+            Commands::Schedule { action } => match action {
+                commands::schedule::command::ScheduleCommand::List => schedule_list(context)?,
+
+                commands::schedule::command::ScheduleCommand::Run { once } => {
+                    schedule_run(context.with_args(ScheduleRunArgs { once }))?;
+                }
+
+                commands::schedule::command::ScheduleCommand::Install { print } => {
+                    schedule_install(context.with_args(ScheduleInstallArgs { print }))?;
+                }
+            },
+
+            Commands::Watch { action } => match action {
+                commands::watch::command::WatchCommand::Start {
+                    path_ids,
+                    tags,
+                    debounce,
+                } => {
+                    watch_start(context.with_args(WatchStartArgs {
+                        path_ids,
+                        tags,
+                        debounce,
+                    }))?;
+                }
+
+                commands::watch::command::WatchCommand::Status => watch_status(context)?,
+            },
+
             Commands::Configure => configure_setup(context)?,
 
             Commands::Completion { shell } => {
@@ -1,9 +1,15 @@
-use crate::log_info;
+use crate::{
+    config::{path_config::PathConfig, rclone::RcloneConfig, template::TemplateConfig},
+    log_info,
+    utils::hash::HashAlgorithm,
+};
 use anyhow::Context;
 use rust_embed::Embed;
 use serde::Deserialize;
 use std::path::PathBuf;
 
+pub const DEFAULT_MAX_DELETE: u32 = 50;
+
 #[derive(Embed)]
 #[folder = "assets/"]
 struct Asset;
@@ -14,11 +20,64 @@ pub struct AppConfig {
 
     #[serde(default)]
     pub tui: TuiConfig,
+
+    #[serde(default)]
+    pub rclone: RcloneConfig,
+
+    #[serde(default)]
+    pub template: TemplateConfig,
 }
 
-#[derive(Debug, Deserialize, Default, Clone)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct CoreConfig {
     pub temp_path: Option<PathBuf>,
+
+    #[serde(default)]
+    pub rclone_args: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub rclone_preset: Option<String>,
+
+    #[serde(default)]
+    pub max_delete: Option<u32>,
+
+    #[serde(default)]
+    pub hash_modes: bool,
+
+    #[serde(default = "CoreConfig::default_hash_cache")]
+    pub hash_cache: bool,
+
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+
+    #[serde(default = "CoreConfig::default_watch_debounce_secs")]
+    pub watch_debounce_secs: u64,
+}
+
+impl CoreConfig {
+    fn default_hash_cache() -> bool {
+        true
+    }
+
+    fn default_watch_debounce_secs() -> u64 {
+        5
+    }
+}
+
+/// Matches the serde defaults, used when the config file cannot be loaded.
+impl Default for CoreConfig {
+    fn default() -> Self {
+        Self {
+            temp_path: None,
+            rclone_args: None,
+            rclone_preset: None,
+            max_delete: None,
+            hash_modes: false,
+            hash_cache: Self::default_hash_cache(),
+            hash_algorithm: HashAlgorithm::default(),
+            watch_debounce_secs: Self::default_watch_debounce_secs(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -49,6 +108,14 @@ impl Default for KeyBindings {
 }
 
 impl AppConfig {
+    /// Maximum number of deletions a mirror is allowed to perform without confirmation.
+    pub fn max_delete(&self, path_config: &PathConfig) -> u32 {
+        path_config
+            .max_delete
+            .or(self.core.max_delete)
+            .unwrap_or(DEFAULT_MAX_DELETE)
+    }
+
     pub fn load(path: &PathBuf) -> anyhow::Result<Self> {
         if !path.exists() {
             Self::create_default_config(path)?;
@@ -1,6 +1,13 @@
 use crate::{
     config::prelude::AppConfig,
-    hooks::prelude::{BackupHook, BackupHookConfig, HookContext, ZipHook, ZipHookConfig},
+    hooks::{
+        artifacts::{Artifacts, artifact_root},
+        prelude::{
+            BackupHook, BackupHookConfig, ChunkHook, ChunkHookConfig, CommandHook,
+            CommandHookConfig, ExternalHook, ExternalHookConfig, HookContext, SqliteHook,
+            SqliteHookConfig, TemplateHook, TemplateHookConfig, ZipHook, ZipHookConfig,
+        },
+    },
     register_hooks,
 };
 use clap::ValueEnum;
@@ -9,12 +16,34 @@ use serde::{Deserialize, Serialize};
 
 pub trait Hook: std::fmt::Debug + Send + Sync {
     fn process(&self, ctx: HookContext, cfg: &AppConfig) -> anyhow::Result<HookContext>;
+
+    /// Describes what `process` would do with `ctx`, one line per step, without
+    /// changing anything. `ctx.path` does not exist when the content is only
+    /// produced by a previous hook or the download.
+    fn explain(&self, _ctx: &HookContext, _cfg: &AppConfig) -> anyhow::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Tracks what `process` created for `output`, removed once the sync is over
+    /// whether it succeeded or failed.
+    ///
+    /// Defaults to the `rcloud-*` temp file or directory holding the output, if any.
+    fn track_artifacts(&self, output: &HookContext, artifacts: &mut Artifacts, cfg: &AppConfig) {
+        if let Some(root) = artifact_root(&output.path, cfg) {
+            artifacts.track(root);
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Selectable)]
 pub enum Hooks {
     Zip,
     Backup,
+    Command,
+    External,
+    Sqlite,
+    Template,
+    Chunk,
 }
 
 impl std::fmt::Display for Hooks {
@@ -22,6 +51,11 @@ impl std::fmt::Display for Hooks {
         match self {
             Hooks::Zip => write!(f, "Zip"),
             Hooks::Backup => write!(f, "Backup"),
+            Hooks::Command => write!(f, "Command"),
+            Hooks::External => write!(f, "External"),
+            Hooks::Sqlite => write!(f, "Sqlite"),
+            Hooks::Template => write!(f, "Template"),
+            Hooks::Chunk => write!(f, "Chunk"),
         }
     }
 }
@@ -46,7 +80,7 @@ register_hooks! {
         config: ZipHookConfig,
         hook: ZipHook,
         enum_type: Hooks::Zip,
-        modifies_name: true,
+        modifies_name: |_: &ZipHookConfig| true,
         display: |cfg: &ZipHookConfig, f: &mut std::fmt::Formatter| write!(f, "Zip(level: {:?})", cfg.level),
         push_desc: "Compress the file/folder before uploading",
         pull_desc: "Extract the file/folder after downloading",
@@ -55,9 +89,66 @@ register_hooks! {
         config: BackupHookConfig,
         hook: BackupHook,
         enum_type: Hooks::Backup,
-        modifies_name: false,
-        display: |cfg: &BackupHookConfig, f: &mut std::fmt::Formatter| write!(f, "Backup(replicas: {})", cfg.replicas),
+        modifies_name: |_: &BackupHookConfig| false,
+        display: |cfg: &BackupHookConfig, f: &mut std::fmt::Formatter| match &cfg.retention {
+            Some(retention) => write!(f, "Backup(replicas: {}, {})", cfg.replicas, retention),
+            None => write!(f, "Backup(replicas: {})", cfg.replicas),
+        },
         push_desc: "Create a backup copy on Local/Remote",
         pull_desc: "Create a backup copy on Local/Remote",
+    },
+    Command {
+        config: CommandHookConfig,
+        hook: CommandHook,
+        enum_type: Hooks::Command,
+        modifies_name: |_: &CommandHookConfig| false,
+        display: |cfg: &CommandHookConfig, f: &mut std::fmt::Formatter| write!(f, "Command({})", cfg.command),
+        push_desc: "Run a shell command before/after uploading",
+        pull_desc: "Run a shell command before/after updating the local path",
+    },
+    External {
+        config: ExternalHookConfig,
+        hook: ExternalHook,
+        enum_type: Hooks::External,
+        modifies_name: |cfg: &ExternalHookConfig| cfg.modifies_filename,
+        display: |cfg: &ExternalHookConfig, f: &mut std::fmt::Formatter| match &cfg.extension {
+            Some(extension) => write!(f, "External({}, extension: {})", cfg.name, extension),
+            None => write!(f, "External({})", cfg.name),
+        },
+        push_desc: "Run an rcloud-hook-<name> plugin before uploading",
+        pull_desc: "Run an rcloud-hook-<name> plugin after downloading",
+    },
+    Sqlite {
+        config: SqliteHookConfig,
+        hook: SqliteHook,
+        enum_type: Hooks::Sqlite,
+        modifies_name: |_: &SqliteHookConfig| false,
+        display: |cfg: &SqliteHookConfig, f: &mut std::fmt::Formatter| match &cfg.extensions {
+            Some(extensions) => write!(f, "Sqlite(extensions: {})", extensions.join(", ")),
+            None => write!(f, "Sqlite"),
+        },
+        push_desc: "Snapshot SQLite databases consistently before uploading",
+        pull_desc: "Check SQLite databases integrity before replacing them",
+    },
+    Template {
+        config: TemplateHookConfig,
+        hook: TemplateHook,
+        enum_type: Hooks::Template,
+        modifies_name: |_: &TemplateHookConfig| false,
+        display: |cfg: &TemplateHookConfig, f: &mut std::fmt::Formatter| write!(f, "Template({})", cfg.patterns.join(", ")),
+        push_desc: "Replace machine values (home, hostname, user...) with placeholders",
+        pull_desc: "Render placeholders with this machine values",
+    },
+    Chunk {
+        config: ChunkHookConfig,
+        hook: ChunkHook,
+        enum_type: Hooks::Chunk,
+        modifies_name: |_: &ChunkHookConfig| true,
+        display: |cfg: &ChunkHookConfig, f: &mut std::fmt::Formatter| match &cfg.part_size {
+            Some(part_size) => write!(f, "Chunk(part size: {})", part_size),
+            None => write!(f, "Chunk"),
+        },
+        push_desc: "Split the file into fixed-size parts before uploading",
+        pull_desc: "Reassemble and verify the parts after downloading",
     }
 }
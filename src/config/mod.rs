@@ -2,6 +2,9 @@ pub mod app;
 pub mod hook_config;
 pub mod path_config;
 pub mod prelude;
+pub mod rclone;
 pub mod registry;
 pub mod remote;
+pub mod remote_filename;
 pub mod tags;
+pub mod template;
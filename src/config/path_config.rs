@@ -1,6 +1,40 @@
-use crate::config::prelude::*;
+use crate::{
+    config::{prelude::*, remote_filename::compute_remote_filename},
+    utils::hash::HashAlgorithm,
+};
+use clap::ValueEnum;
+use inquire_derive::Selectable;
 use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Selectable, PartialEq, ValueEnum)]
+pub enum SyncMode {
+    #[default]
+    Copy,
+    Mirror,
+    Move,
+}
+
+impl SyncMode {
+    /// The rclone subcommand used to transfer data in this mode.
+    pub fn rclone_command(&self) -> &'static str {
+        match self {
+            SyncMode::Copy => "copy",
+            SyncMode::Mirror => "sync",
+            SyncMode::Move => "move",
+        }
+    }
+}
+
+impl std::fmt::Display for SyncMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncMode::Copy => write!(f, "Copy"),
+            SyncMode::Mirror => write!(f, "Mirror"),
+            SyncMode::Move => write!(f, "Move"),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PathConfig {
     pub id: String,
@@ -12,10 +46,41 @@ pub struct PathConfig {
     #[serde(default)]
     pub hash: Option<String>,
 
+    #[serde(default)]
+    pub hash_version: Option<u32>,
+
+    #[serde(default)]
+    pub hash_algorithm: Option<HashAlgorithm>,
+
     #[serde(default)]
     pub tags: Vec<String>,
 
+    /// Cron expression or interval (`every 6h`) for `rcloud schedule run`.
+    #[serde(default)]
+    pub schedule: Option<String>,
+
+    #[serde(default)]
+    pub mode: SyncMode,
+
+    #[serde(default)]
+    pub max_delete: Option<u32>,
+
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    #[serde(default)]
+    pub ignore_file: Option<String>,
+
     pub hooks: PathConfigHooks,
+
+    #[serde(default)]
+    pub rclone_args: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub rclone_preset: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -26,3 +91,34 @@ pub struct PathConfigHooks {
     #[serde(default)]
     pub pull: Vec<HookConfig>,
 }
+
+impl PathConfigHooks {
+    pub fn by_exec(&self, exec: HookExecType) -> &[HookConfig] {
+        match exec {
+            HookExecType::Push => &self.push,
+            HookExecType::Pull => &self.pull,
+        }
+    }
+}
+
+impl PathConfig {
+    /// Remote path of the synced content, relative to the remote root.
+    ///
+    /// This is `remote_path` itself, or the artifact pushed inside it
+    /// (e.g. `remote_path/name.zip`) when one of `hooks` renames the content.
+    pub fn remote_object(&self, hooks: &[HookConfig]) -> String {
+        if !hooks.iter().any(|h| h.modifies_filename()) {
+            return self.remote_path.clone();
+        }
+
+        let filename = compute_remote_filename(
+            hooks,
+            std::path::Path::new(&self.remote_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("archive"),
+        );
+
+        format!("{}/{}", self.remote_path.trim_end_matches('/'), filename)
+    }
+}
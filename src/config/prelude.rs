@@ -1,6 +1,6 @@
 pub use super::app::AppConfig;
 pub use super::hook_config::{Hook, HookConfig, HookExecType, Hooks};
-pub use super::path_config::{PathConfig, PathConfigHooks};
+pub use super::path_config::{PathConfig, PathConfigHooks, SyncMode};
 pub use super::registry::Registry;
 pub use super::remote::Remote;
 pub use super::tags::TagOption;
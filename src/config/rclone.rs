@@ -0,0 +1,85 @@
+use crate::config::prelude::{AppConfig, PathConfig, Remote};
+use anyhow::Context;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct RcloneConfig {
+    #[serde(default)]
+    pub presets: HashMap<String, RclonePreset>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct RclonePreset {
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl RcloneConfig {
+    /// Expands a single configuration level (preset first, then explicit args).
+    fn expand(
+        &self,
+        preset: Option<&String>,
+        args: Option<&Vec<String>>,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut expanded = Vec::new();
+
+        if let Some(name) = preset {
+            let preset = self
+                .presets
+                .get(name)
+                .with_context(|| format!("rclone preset not found: {}", name))?;
+
+            expanded.extend(preset.args.iter().cloned());
+        }
+
+        if let Some(args) = args {
+            expanded.extend(args.iter().cloned());
+        }
+
+        Ok(expanded)
+    }
+}
+
+impl AppConfig {
+    /// Resolves the extra rclone arguments for a given path.
+    ///
+    /// Arguments are merged from the most generic to the most specific level:
+    /// `core`, then the `Remote`, then the `PathConfig`, each level expanding its
+    /// preset before its own args. Since rclone keeps the last value of a repeated
+    /// flag, a path overrides its remote, which overrides `core`.
+    pub fn rclone_args(
+        &self,
+        remote: &Remote,
+        path_config: &PathConfig,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut args = self
+            .rclone
+            .expand(
+                self.core.rclone_preset.as_ref(),
+                self.core.rclone_args.as_ref(),
+            )
+            .context("failed to resolve core rclone args")?;
+
+        args.extend(
+            self.rclone
+                .expand(remote.rclone_preset.as_ref(), remote.rclone_args.as_ref())
+                .with_context(|| {
+                    format!("failed to resolve rclone args for remote: {}", remote.id)
+                })?,
+        );
+
+        args.extend(
+            self.rclone
+                .expand(
+                    path_config.rclone_preset.as_ref(),
+                    path_config.rclone_args.as_ref(),
+                )
+                .with_context(|| {
+                    format!("failed to resolve rclone args for path: {}", path_config.id)
+                })?,
+        );
+
+        Ok(args)
+    }
+}
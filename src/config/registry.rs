@@ -1,4 +1,4 @@
-use crate::{config::prelude::*, log_debug, log_info, log_warn};
+use crate::{config::prelude::*, hooks::condition::validate_when, log_debug, log_info, log_warn};
 use anyhow::{Context, bail};
 use fs2::FileExt;
 use serde::{Deserialize, Serialize};
@@ -82,6 +82,15 @@ impl Registry {
             Ok(mut loaded) => {
                 loaded.registry_path = registry_path.clone();
                 log_debug!("file loaded");
+
+                for path in &loaded.paths {
+                    for hook in path.hooks.push.iter().chain(&path.hooks.pull) {
+                        if let Err(e) = validate_when(hook) {
+                            log_warn!("path {}: {}, its syncs will fail", path.id, e);
+                        }
+                    }
+                }
+
                 Ok(loaded)
             }
             Err(err) => {
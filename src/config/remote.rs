@@ -5,4 +5,10 @@ pub struct Remote {
     pub id: String,
     pub remote_name: String,
     pub provider: String,
+
+    #[serde(default)]
+    pub rclone_args: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub rclone_preset: Option<String>,
 }
@@ -0,0 +1,46 @@
+use crate::{config::prelude::HookConfig, hooks::chunk::PARTS_EXTENSION};
+
+/// Computes the final remote filename based on the applied hooks.
+///
+/// # Parameters
+/// - `hooks`: Slice of hooks to be applied to the file.
+/// - `base_name`: The base name of the remote file.
+///
+/// # Returns
+/// A `String` containing the final remote filename, including the extension if required.
+/// A `Chunk` hook wraps the name computed by the hooks before it, e.g. `backup.zip.parts`.
+///
+/// # Example
+/// ```rust, ignore
+/// let hooks = vec![HookConfig::Zip(Default::default())];
+/// let filename = compute_remote_filename(&hooks, "backup");
+/// assert_eq!(filename, "backup.zip");
+/// ```
+pub fn compute_remote_filename(hooks: &[HookConfig], base_name: &str) -> String {
+    let Some(last) = hooks.iter().rposition(|hook| hook.modifies_filename()) else {
+        return base_name.to_string();
+    };
+
+    match &hooks[last] {
+        HookConfig::Chunk(_) => format!(
+            "{}.{}",
+            compute_remote_filename(&hooks[..last], base_name),
+            PARTS_EXTENSION
+        ),
+        HookConfig::Zip(_) => format!("{}.zip", base_name),
+        HookConfig::External(cfg) => match &cfg.extension {
+            Some(extension) => format!("{}.{}", base_name, extension),
+            None => base_name.to_string(),
+        },
+        _ => base_name.to_string(),
+    }
+}
+
+/// Whether the hooks turn the content into a directory of parts, synced as
+/// `remote_path/<remote filename>` instead of a single file.
+pub fn is_multipart(hooks: &[HookConfig]) -> bool {
+    matches!(
+        hooks.iter().rfind(|hook| hook.modifies_filename()),
+        Some(HookConfig::Chunk(_))
+    )
+}
@@ -0,0 +1,62 @@
+use crate::{
+    config::prelude::AppConfig,
+    utils::host::{hostname, user},
+};
+use anyhow::Context;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Placeholders are written as `${RCLOUD_<NAME>}`, e.g. `${RCLOUD_HOME}`.
+pub const PLACEHOLDER_PREFIX: &str = "RCLOUD_";
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct TemplateConfig {
+    /// Machine values substituted by `Template` hooks, keyed by variable name.
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+/// Name of the placeholder of a variable, e.g. `home` -> `RCLOUD_HOME`.
+pub fn placeholder_name(variable: &str) -> String {
+    format!("{}{}", PLACEHOLDER_PREFIX, variable.to_uppercase())
+}
+
+impl AppConfig {
+    /// Values rendered by `Template` hooks on this machine, keyed by placeholder name.
+    ///
+    /// `home`, `hostname` and `user` are detected, `[template.variables]` can
+    /// override them and declare others. Values are expanded with `shellexpand`,
+    /// so `~` and `$VAR` can be used. Empty values are left out.
+    pub fn template_variables(&self) -> anyhow::Result<BTreeMap<String, String>> {
+        let mut variables = BTreeMap::new();
+
+        let detected = [
+            ("home", Some(shellexpand::tilde("~").to_string())),
+            ("hostname", hostname()),
+            ("user", user()),
+        ];
+
+        for (name, value) in detected {
+            if let Some(value) = value {
+                variables.insert(placeholder_name(name), value);
+            }
+        }
+
+        for (name, value) in &self.template.variables {
+            anyhow::ensure!(
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'),
+                "invalid template variable name: {}",
+                name
+            );
+
+            let value = shellexpand::full(value)
+                .with_context(|| format!("failed to expand template variable: {}", name))?;
+
+            variables.insert(placeholder_name(name), value.to_string());
+        }
+
+        variables.retain(|_, value| !value.is_empty());
+
+        Ok(variables)
+    }
+}
@@ -0,0 +1,282 @@
+use crate::{
+    config::prelude::AppConfig,
+    hooks::{
+        chunk::hook::CHUNK_PREFIX,
+        external::hook::WORK_DIR_PREFIX,
+        prelude::ZipHook,
+        sqlite::hook::SNAPSHOT_PREFIX,
+        template::hook::TEMPLATE_PREFIX,
+        zip::hook::{EXTRACT_PREFIX, ZIP_PREFIX},
+    },
+    log_debug, log_warn,
+};
+use anyhow::Context;
+use fs2::FileExt;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{LazyLock, Mutex},
+    time::{Duration, SystemTime},
+};
+
+/// Prefix of the directories pulls and restores download into.
+pub const DOWNLOAD_PREFIX: &str = "rcloud-download-";
+
+/// Prefix of the directories incremental backups stage new chunks in.
+pub const STAGING_PREFIX: &str = "rcloud-staging-";
+
+/// Prefixes of everything rcloud creates in the temp directory.
+pub const ARTIFACT_PREFIXES: [&str; 8] = [
+    ZIP_PREFIX,
+    EXTRACT_PREFIX,
+    SNAPSHOT_PREFIX,
+    TEMPLATE_PREFIX,
+    CHUNK_PREFIX,
+    WORK_DIR_PREFIX,
+    DOWNLOAD_PREFIX,
+    STAGING_PREFIX,
+];
+
+/// Directory artifacts are created in: `core.temp_path` or the system temp dir.
+pub fn temp_root(cfg: &AppConfig) -> anyhow::Result<PathBuf> {
+    Ok(ZipHook::base_temp_dir(cfg)?.unwrap_or_else(std::env::temp_dir))
+}
+
+fn is_artifact_name(name: &str) -> bool {
+    ARTIFACT_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// The artifact holding `path`: `path` itself or its ancestor created in the temp root.
+pub fn artifact_root(path: &Path, cfg: &AppConfig) -> Option<PathBuf> {
+    let root = temp_root(cfg).ok()?;
+
+    path.ancestors()
+        .find(|ancestor| {
+            ancestor.parent() == Some(root.as_path())
+                && ancestor
+                    .file_name()
+                    .is_some_and(|name| is_artifact_name(&name.to_string_lossy()))
+        })
+        .map(Path::to_path_buf)
+}
+
+/// Locks of the artifacts kept past their `ArtifactDir`, released once removed.
+static KEPT: LazyLock<Mutex<HashMap<PathBuf, InUse>>> = LazyLock::new(Default::default);
+
+/// Lock file next to `artifact`, hidden so it is not listed as an artifact itself.
+fn marker_path(artifact: &Path) -> Option<PathBuf> {
+    let name = artifact.file_name()?.to_string_lossy();
+
+    Some(artifact.with_file_name(format!(".{}.lock", name)))
+}
+
+/// Exclusive lock on an artifact marker, telling `gc` the artifact is in use.
+///
+/// The lock goes away with the process, so a crashed sync does not keep its
+/// artifacts forever.
+#[derive(Debug)]
+struct InUse {
+    file: std::fs::File,
+    marker: PathBuf,
+}
+
+impl InUse {
+    fn acquire(artifact: &Path) -> anyhow::Result<Self> {
+        let marker = marker_path(artifact).context("artifact has no file name")?;
+        let file = std::fs::File::create(&marker)
+            .with_context(|| format!("failed to create {:?}", marker))?;
+
+        file.try_lock_exclusive()
+            .with_context(|| format!("failed to lock {:?}", marker))?;
+
+        Ok(Self { file, marker })
+    }
+}
+
+impl Drop for InUse {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+        let _ = std::fs::remove_file(&self.marker);
+    }
+}
+
+/// Whether the process that created `artifact` still holds its marker.
+fn is_in_use(artifact: &Path) -> bool {
+    let Some(file) = marker_path(artifact).and_then(|m| std::fs::File::open(m).ok()) else {
+        return false;
+    };
+
+    match file.try_lock_exclusive() {
+        Ok(()) => {
+            let _ = FileExt::unlock(&file);
+            false
+        }
+        Err(_) => true,
+    }
+}
+
+/// Temp directory created in the temp root, marked as in use until removed.
+#[derive(Debug)]
+pub struct ArtifactDir {
+    dir: tempfile::TempDir,
+    in_use: InUse,
+}
+
+impl ArtifactDir {
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Keeps the directory past this value, it stays marked until `remove` is called on it.
+    pub fn keep(self) -> PathBuf {
+        let path = self.dir.keep();
+
+        if let Ok(mut kept) = KEPT.lock() {
+            kept.insert(path.clone(), self.in_use);
+        }
+
+        path
+    }
+}
+
+/// Creates a `prefix*` directory in the temp root, skipped by `gc` while in use.
+pub fn create_dir(prefix: &str, cfg: &AppConfig) -> anyhow::Result<ArtifactDir> {
+    let dir = tempfile::Builder::new()
+        .prefix(prefix)
+        .tempdir_in(temp_root(cfg)?)
+        .context("failed to create temp dir")?;
+    let in_use = InUse::acquire(dir.path())?;
+
+    Ok(ArtifactDir { dir, in_use })
+}
+
+/// Removes a file or directory, succeeding if it is already gone.
+///
+/// The marker of a removed artifact goes with it.
+pub fn remove(path: &Path) -> anyhow::Result<()> {
+    let result = match path.is_dir() {
+        true => std::fs::remove_dir_all(path),
+        false => std::fs::remove_file(path),
+    };
+
+    match result {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("failed to remove {:?}", path))
+        }
+        _ => {
+            release(path);
+            Ok(())
+        }
+    }
+}
+
+fn release(path: &Path) {
+    let kept = KEPT.lock().ok().and_then(|mut kept| kept.remove(path));
+
+    if kept.is_none()
+        && let Some(marker) = marker_path(path)
+    {
+        let _ = std::fs::remove_file(marker);
+    }
+}
+
+/// Size in bytes of the files under `path`.
+fn disk_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Artifact left in the temp root, usually by an interrupted sync.
+#[derive(Debug, Clone)]
+pub struct StaleArtifact {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// Lists the artifacts of the temp root untouched for at least `older_than`.
+///
+/// Artifacts still marked by a running sync are left out, and so are recent
+/// ones, which may not be marked yet.
+pub fn stale_artifacts(
+    cfg: &AppConfig,
+    older_than: Duration,
+) -> anyhow::Result<Vec<StaleArtifact>> {
+    let root = temp_root(cfg)?;
+    let now = SystemTime::now();
+    let mut stale = Vec::new();
+
+    for entry in std::fs::read_dir(&root).with_context(|| format!("failed to read {:?}", root))? {
+        let entry = entry.with_context(|| format!("failed to read {:?}", root))?;
+
+        if !is_artifact_name(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+
+        let Ok(modified) = entry.metadata().and_then(|m| m.modified()) else {
+            continue;
+        };
+
+        if now.duration_since(modified).unwrap_or_default() < older_than {
+            log_debug!("keeping recent artifact: {:?}", entry.path());
+            continue;
+        }
+
+        if is_in_use(&entry.path()) {
+            log_debug!("keeping artifact in use: {:?}", entry.path());
+            continue;
+        }
+
+        stale.push(StaleArtifact {
+            size: disk_size(&entry.path()),
+            path: entry.path(),
+            modified,
+        });
+    }
+
+    stale.sort_by_key(|artifact| artifact.modified);
+
+    Ok(stale)
+}
+
+/// Paths to remove once a sync is over, removed when dropped so failed syncs clean up too.
+#[derive(Debug, Default)]
+pub struct Artifacts {
+    paths: Vec<PathBuf>,
+}
+
+impl Artifacts {
+    pub fn track(&mut self, path: PathBuf) {
+        if !self.paths.contains(&path) {
+            log_debug!("tracking artifact: {:?}", path);
+            self.paths.push(path);
+        }
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Removes the tracked paths, newest first, warning about the ones that remain.
+    pub fn cleanup(&mut self) {
+        while let Some(path) = self.paths.pop() {
+            match remove(&path) {
+                Ok(()) => log_debug!("removed artifact: {:?}", path),
+                Err(e) => log_warn!("{:#}", e),
+            }
+        }
+    }
+}
+
+impl Drop for Artifacts {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
@@ -1,6 +1,15 @@
-use crate::hooks::{
-    backup::{BackupHook, utils},
-    prelude::HookContext,
+use crate::{
+    config::prelude::{AppConfig, Remote},
+    hooks::{
+        backup::{
+            BackupHook, BackupType,
+            incremental::{local, remote},
+            retention::{RetentionPolicy, take_replicas},
+            utils,
+        },
+        prelude::HookContext,
+    },
+    log_debug,
 };
 use anyhow::Context;
 
@@ -11,50 +20,212 @@ pub struct BackupHookReplica {
     pub replica_number: u32,
 }
 
+fn next_replica_number(replicas: &[BackupHookReplica]) -> u32 {
+    replicas
+        .iter()
+        .map(|r| r.replica_number + 1)
+        .max()
+        .unwrap_or(1)
+}
+
 impl BackupHook {
-    pub fn backup_local(&self, ctx: &HookContext) -> anyhow::Result<()> {
-        let local_path = self
-            .local_path
-            .as_deref()
-            .context("remote path must be declared in order to perform a remote backup")?;
+    pub fn retention_policy(&self) -> RetentionPolicy {
+        self.retention.clone().unwrap_or_default()
+    }
+
+    /// Local directory or remote path holding the replicas of a backup type.
+    pub fn location(&self, backup_type: BackupType) -> Option<&str> {
+        match backup_type {
+            BackupType::Local | BackupType::LocalIncremental => self.local_path.as_deref(),
+            BackupType::Remote | BackupType::RemoteIncremental => self.remote_path.as_deref(),
+        }
+    }
 
-        let mut replicas =
-            utils::get_local_replicas(local_path).context("failed to get local replicas")?;
+    fn required_location(&self, backup_type: BackupType) -> anyhow::Result<&str> {
+        self.location(backup_type)
+            .with_context(|| match backup_type {
+                BackupType::Local | BackupType::LocalIncremental => {
+                    "local path must be declared in order to perform a local backup"
+                }
+                BackupType::Remote | BackupType::RemoteIncremental => {
+                    "remote path must be declared in order to perform a remote backup"
+                }
+            })
+    }
 
-        utils::rotate_local_replicas(&mut replicas, self.replicas as usize)
-            .context("failed to rotate local replicas")?;
+    /// Lists the replicas of a backup type, in no particular order.
+    pub fn replicas(
+        &self,
+        backup_type: BackupType,
+        rclone_path: &str,
+        remote_config: &Remote,
+    ) -> anyhow::Result<Vec<BackupHookReplica>> {
+        let location = self.required_location(backup_type)?;
 
-        let next_replica = replicas.first().map(|r| r.replica_number + 1).unwrap_or(1);
+        match backup_type {
+            BackupType::Local => {
+                utils::get_local_replicas(location).context("failed to get local replicas")
+            }
+            BackupType::Remote => utils::get_remote_replicas(location, rclone_path, remote_config)
+                .context("failed to get remote replicas"),
+            BackupType::LocalIncremental => local::get_local_incremental_replicas(location)
+                .context("failed to get local incremental replicas"),
+            BackupType::RemoteIncremental => {
+                remote::get_remote_incremental_replicas(location, rclone_path, remote_config)
+                    .context("failed to get remote incremental replicas")
+            }
+        }
+    }
 
-        utils::create_local_backup(ctx, local_path, next_replica)
-            .context("failed to create local backup")?;
+    /// Removes the replicas of a backup type not kept by the retention policy.
+    ///
+    /// # Returns
+    /// The pruned replicas, oldest first. Nothing is removed when `dry_run` is set.
+    pub fn prune_replicas(
+        &self,
+        backup_type: BackupType,
+        rclone_path: &str,
+        remote_config: &Remote,
+        dry_run: bool,
+        cfg: &AppConfig,
+    ) -> anyhow::Result<Vec<BackupHookReplica>> {
+        let location = self.required_location(backup_type)?;
+        let replicas = self.replicas(backup_type, rclone_path, remote_config)?;
+        let retention = self.retention_policy();
+        let max_replicas = self.replicas as usize;
 
-        Ok(())
+        match backup_type {
+            BackupType::Local => {
+                utils::rotate_local_replicas(replicas, max_replicas, &retention, dry_run)
+                    .context("failed to rotate local replicas")
+            }
+            BackupType::Remote => utils::rotate_remote_replicas(
+                replicas,
+                max_replicas,
+                &retention,
+                dry_run,
+                rclone_path,
+                remote_config,
+                location,
+            )
+            .context("failed to rotate remote replicas"),
+            BackupType::LocalIncremental => local::rotate_local_incremental_replicas(
+                replicas,
+                max_replicas,
+                &retention,
+                dry_run,
+                location,
+            )
+            .context("failed to rotate local incremental replicas"),
+            BackupType::RemoteIncremental => {
+                let pruned = remote::rotate_remote_incremental_replicas(
+                    replicas,
+                    max_replicas,
+                    &retention,
+                    dry_run,
+                    rclone_path,
+                    remote_config,
+                    location,
+                )
+                .context("failed to rotate remote incremental replicas")?;
+
+                if !dry_run && !pruned.is_empty() {
+                    let removed =
+                        remote::collect_garbage(location, rclone_path, remote_config, cfg)
+                            .context("failed to remove orphaned chunks")?;
+
+                    log_debug!("removed {} orphaned chunk(s)", removed);
+                }
+
+                Ok(pruned)
+            }
+        }
     }
 
-    pub fn backup_remote(&self, ctx: &HookContext) -> anyhow::Result<()> {
-        let remote_path = self
-            .remote_path
-            .as_deref()
-            .context("remote path must be declared in order to perform a remote backup")?;
+    /// Replicas of a backup type the next backup would rotate out, nothing is created or removed.
+    ///
+    /// Replica sizes are only known once the next one is written, `max_size` is not previewed.
+    pub fn rotation_preview(
+        &self,
+        backup_type: BackupType,
+        rclone_path: &str,
+        remote_config: &Remote,
+    ) -> anyhow::Result<Vec<BackupHookReplica>> {
+        let mut replicas = self.replicas(backup_type, rclone_path, remote_config)?;
+        let now = chrono::Utc::now();
 
-        let mut replicas =
-            utils::get_remote_replicas(remote_path, &ctx.rclone_path, &ctx.remote_config)
-                .context("failed to get remote replicas")?;
+        // placeholder for the replica the next backup creates, always the newest
+        replicas.push(BackupHookReplica {
+            path: std::path::PathBuf::new(),
+            timestamp: now.timestamp().max(0) as u64,
+            replica_number: next_replica_number(&replicas),
+        });
 
-        utils::rotate_remote_replicas(
-            &mut replicas,
+        let prunable = self.retention_policy().select_prunable(
+            &replicas,
             self.replicas as usize,
-            &ctx.rclone_path,
-            &ctx.remote_config,
-            remote_path,
-        )
-        .context("failed to rotate remote replicas")?;
+            now,
+            |_| Ok(0),
+        )?;
+
+        Ok(take_replicas(replicas, &prunable))
+    }
 
-        let next_replica = replicas.first().map(|r| r.replica_number + 1).unwrap_or(1);
+    /// Creates a replica of `ctx.path` (or of the remote path for `Remote`), then rotates.
+    pub fn backup(
+        &self,
+        backup_type: BackupType,
+        ctx: &HookContext,
+        cfg: &AppConfig,
+    ) -> anyhow::Result<()> {
+        let location = self.required_location(backup_type)?;
+        let replicas = self.replicas(backup_type, &ctx.rclone_path, &ctx.remote_config)?;
+        let next_replica = next_replica_number(&replicas);
 
-        utils::create_remote_backup(ctx, remote_path, next_replica)
-            .context("failed to create remote backup")?;
+        match backup_type {
+            BackupType::Local => {
+                let previous = replicas.iter().max_by_key(|r| r.timestamp);
+
+                utils::create_local_backup(
+                    ctx,
+                    location,
+                    next_replica,
+                    self.snapshot.unwrap_or_default(),
+                    previous,
+                )
+                .context("failed to create local backup")?
+            }
+            BackupType::Remote => {
+                let hooks = ctx.path_config.hooks.by_exec(self.exec);
+
+                if !utils::create_remote_backup(ctx, hooks, location, next_replica)
+                    .context("failed to create remote backup")?
+                {
+                    return Ok(());
+                }
+            }
+            BackupType::LocalIncremental => {
+                local::create_local_incremental_backup(&ctx.path, location, next_replica)
+                    .context("failed to create local incremental backup")?
+            }
+            BackupType::RemoteIncremental => remote::create_remote_incremental_backup(
+                &ctx.path,
+                location,
+                next_replica,
+                &ctx.rclone_path,
+                &ctx.remote_config,
+                cfg,
+            )
+            .context("failed to create remote incremental backup")?,
+        }
+
+        self.prune_replicas(
+            backup_type,
+            &ctx.rclone_path,
+            &ctx.remote_config,
+            false,
+            cfg,
+        )?;
 
         Ok(())
     }
@@ -1,6 +1,6 @@
 use crate::{
     config::prelude::{HookConfig, HookExecType, Hooks},
-    hooks::backup::{BackupHookConfig, BackupType},
+    hooks::backup::{BackupHookConfig, BackupType, SnapshotMode, retention::RetentionPolicy},
     log_info, utils,
 };
 use anyhow::Context;
@@ -8,10 +8,10 @@ use anyhow::Context;
 impl BackupHookConfig {
     fn prompt_if(
         types: &[BackupType],
-        variant: BackupType,
+        variants: &[BackupType],
         prompt: &str,
     ) -> anyhow::Result<Option<String>> {
-        if types.contains(&variant) {
+        if let Some(variant) = variants.iter().find(|v| types.contains(v)) {
             return Ok(Some(
                 inquire::Text::new(prompt)
                     .prompt()
@@ -22,6 +22,52 @@ impl BackupHookConfig {
         Ok(None)
     }
 
+    fn prompt_count(prompt: &str) -> anyhow::Result<u32> {
+        inquire::Text::new(prompt)
+            .with_default("0")
+            .prompt()
+            .with_context(|| format!("failed to get {}", prompt))?
+            .parse::<u32>()
+            .context("not a number")
+    }
+
+    fn prompt_optional(prompt: &str, help: &str) -> anyhow::Result<Option<String>> {
+        let value = inquire::Text::new(prompt)
+            .with_help_message(help)
+            .prompt()
+            .with_context(|| format!("failed to get {}", prompt))?;
+
+        Ok(Some(value.trim().to_string()).filter(|v| !v.is_empty()))
+    }
+
+    fn prompt_retention() -> anyhow::Result<Option<RetentionPolicy>> {
+        let configure = inquire::Confirm::new("Configure a retention policy?")
+            .with_default(false)
+            .with_help_message("Keep hourly/daily/weekly/monthly replicas, limit age or total size")
+            .prompt()
+            .context("failed to get confirmation")?;
+
+        if !configure {
+            return Ok(None);
+        }
+
+        let retention = RetentionPolicy {
+            keep_hourly: Self::prompt_count("Hourly replicas to keep:")?,
+            keep_daily: Self::prompt_count("Daily replicas to keep:")?,
+            keep_weekly: Self::prompt_count("Weekly replicas to keep:")?,
+            keep_monthly: Self::prompt_count("Monthly replicas to keep:")?,
+            max_age: Self::prompt_optional("Max age:", "e.g. 30d or 12w, empty for no limit")?,
+            max_size: Self::prompt_optional(
+                "Max total size:",
+                "e.g. 500M or 10G, empty for no limit",
+            )?,
+        };
+
+        retention.validate().context("invalid retention policy")?;
+
+        Ok(Some(retention))
+    }
+
     pub fn build(exec_type: HookExecType) -> anyhow::Result<HookConfig> {
         log_info!("configuring {} for {}", Hooks::Backup, exec_type);
 
@@ -29,9 +75,12 @@ impl BackupHookConfig {
             .prompt()
             .context("failed to select backup types")?;
 
-        let local_path =
-            BackupHookConfig::prompt_if(&types, BackupType::Local, "Local Backup path:")
-                .context("failed to get local path")?;
+        let local_path = BackupHookConfig::prompt_if(
+            &types,
+            &[BackupType::Local, BackupType::LocalIncremental],
+            "Local Backup path:",
+        )
+        .context("failed to get local path")?;
 
         let local_path = match local_path {
             Some(value) => Some(
@@ -42,9 +91,12 @@ impl BackupHookConfig {
             None => None,
         };
 
-        let remote_path =
-            BackupHookConfig::prompt_if(&types, BackupType::Remote, "Remote Backup path:")
-                .context("failed to get remote path")?;
+        let remote_path = BackupHookConfig::prompt_if(
+            &types,
+            &[BackupType::Remote, BackupType::RemoteIncremental],
+            "Remote Backup path:",
+        )
+        .context("failed to get remote path")?;
 
         let replicas = inquire::Text::new("Max replicas:")
             .with_default("1")
@@ -53,12 +105,29 @@ impl BackupHookConfig {
             .parse::<u32>()
             .context("not a number")?;
 
+        let snapshot = match types.contains(&BackupType::Local) {
+            true => Some(
+                SnapshotMode::select("Local snapshot mode:")
+                    .with_help_message(
+                        "reflink clones files, hardlink shares files unchanged since the previous replica, auto picks one",
+                    )
+                    .prompt()
+                    .context("failed to select snapshot mode")?,
+            ),
+            false => None,
+        };
+
+        let retention = Self::prompt_retention()?;
+
         Ok(HookConfig::Backup(Self {
             exec: exec_type,
             types,
             local_path,
             remote_path,
             replicas,
+            retention,
+            snapshot,
+            when: None,
         }))
     }
 }
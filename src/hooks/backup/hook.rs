@@ -1,7 +1,7 @@
 use crate::{
     config::prelude::{AppConfig, Hook, HookExecType},
     define_hook,
-    hooks::prelude::HookContext,
+    hooks::{backup::retention::RetentionPolicy, prelude::HookContext},
     log_info,
 };
 use inquire_derive::Selectable;
@@ -11,6 +11,8 @@ use serde::{Deserialize, Serialize};
 pub enum BackupType {
     Local,
     Remote,
+    LocalIncremental,
+    RemoteIncremental,
 }
 
 impl std::fmt::Display for BackupType {
@@ -18,6 +20,30 @@ impl std::fmt::Display for BackupType {
         match self {
             BackupType::Local => write!(f, "Local"),
             BackupType::Remote => write!(f, "Remote"),
+            BackupType::LocalIncremental => write!(f, "Local (incremental)"),
+            BackupType::RemoteIncremental => write!(f, "Remote (incremental)"),
+        }
+    }
+}
+
+/// How local replicas are written.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Selectable, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotMode {
+    #[default]
+    Copy,
+    Reflink,
+    Hardlink,
+    Auto,
+}
+
+impl std::fmt::Display for SnapshotMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotMode::Copy => write!(f, "copy"),
+            SnapshotMode::Reflink => write!(f, "reflink"),
+            SnapshotMode::Hardlink => write!(f, "hardlink"),
+            SnapshotMode::Auto => write!(f, "auto"),
         }
     }
 }
@@ -27,10 +53,12 @@ define_hook!(BackupHook {
     local_path: Option<String>,
     remote_path: Option<String>,
     replicas: u32,
+    retention: Option<RetentionPolicy>,
+    snapshot: Option<SnapshotMode>,
 });
 
 impl Hook for BackupHook {
-    fn process(&self, ctx: HookContext, _cfg: &AppConfig) -> anyhow::Result<HookContext> {
+    fn process(&self, ctx: HookContext, cfg: &AppConfig) -> anyhow::Result<HookContext> {
         anyhow::ensure!(
             ctx.file_exists(),
             "source file does not exist: {:?}",
@@ -41,10 +69,10 @@ impl Hook for BackupHook {
             log_info!("executing backup {} in {}", backup_type, self.exec);
 
             match (backup_type, &self.exec) {
-                (BackupType::Local, HookExecType::Push) => {
-                    self.backup_local(&ctx)?;
+                (BackupType::Remote, _) | (_, HookExecType::Push) => {
+                    self.backup(*backup_type, &ctx, cfg)?;
                 }
-                (BackupType::Local, HookExecType::Pull) => {
+                (_, HookExecType::Pull) => {
                     let local_path = std::path::PathBuf::from(&ctx.path_config.local_path);
 
                     if !local_path.exists() {
@@ -56,22 +84,72 @@ impl Hook for BackupHook {
                         continue;
                     }
 
-                    self.backup_local(&HookContext::new(
-                        local_path,
-                        &ctx.rclone_path,
-                        &ctx.remote_config,
-                        &ctx.path_config,
-                    ))?;
-                }
-                (BackupType::Remote, HookExecType::Push) => {
-                    self.backup_remote(&ctx)?;
-                }
-                (BackupType::Remote, HookExecType::Pull) => {
-                    self.backup_remote(&ctx)?;
+                    self.backup(
+                        *backup_type,
+                        &HookContext::new(
+                            local_path,
+                            &ctx.rclone_path,
+                            &ctx.remote_config,
+                            &ctx.path_config,
+                        ),
+                        cfg,
+                    )?;
                 }
             }
         }
 
         Ok(ctx)
     }
+
+    fn explain(&self, ctx: &HookContext, _cfg: &AppConfig) -> anyhow::Result<Vec<String>> {
+        let mut lines = Vec::new();
+
+        for backup_type in &self.types {
+            let source = match (backup_type, &self.exec) {
+                (BackupType::Remote, _) => format!(
+                    "{}:{}",
+                    ctx.remote_config.remote_name,
+                    ctx.path_config
+                        .remote_object(ctx.path_config.hooks.by_exec(self.exec))
+                ),
+                (_, HookExecType::Push) if ctx.path.exists() => ctx.path.display().to_string(),
+                (_, HookExecType::Push) => String::from("the content of the previous hook"),
+                (_, HookExecType::Pull) => ctx.path_config.local_path.clone(),
+            };
+
+            lines.push(format!(
+                "{} replica of {} in {} (keeps {}{})",
+                backup_type,
+                source,
+                self.location(*backup_type).unwrap_or("<missing location>"),
+                self.replicas,
+                self.retention
+                    .as_ref()
+                    .map(|retention| format!(", {}", retention))
+                    .unwrap_or_default()
+            ));
+
+            match self.rotation_preview(*backup_type, &ctx.rclone_path, &ctx.remote_config) {
+                Ok(rotated) if rotated.is_empty() => {
+                    lines.push(String::from("  no replica rotated out"))
+                }
+                Ok(rotated) => lines.extend(rotated.iter().map(|replica| {
+                    format!(
+                        "  - rotates out replica {} from {}",
+                        replica.replica_number,
+                        chrono::DateTime::from_timestamp(replica.timestamp as i64, 0)
+                            .map(|dt| {
+                                dt.with_timezone(&chrono::Local)
+                                    .format("%Y-%m-%d %H:%M:%S")
+                                    .to_string()
+                            })
+                            .unwrap_or_default()
+                    )
+                })),
+                Err(e) => lines.push(format!("  could not list the replicas: {:#}", e)),
+            }
+        }
+
+        Ok(lines)
+    }
 }
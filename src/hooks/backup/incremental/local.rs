@@ -0,0 +1,145 @@
+use crate::{
+    hooks::backup::{
+        backup_hook::BackupHookReplica,
+        incremental::{
+            ChunkStore, MANIFEST_PATTERN, MANIFESTS_DIR, Manifest, manifest::manifest_name,
+        },
+        retention::{RetentionPolicy, take_replicas},
+        utils,
+    },
+    log_debug,
+};
+use anyhow::Context;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+pub fn get_local_incremental_replicas(root: &str) -> anyhow::Result<Vec<BackupHookReplica>> {
+    let directory = Path::new(root).join(MANIFESTS_DIR);
+
+    let mut replicas = Vec::new();
+    let re = regex::Regex::new(MANIFEST_PATTERN).context("failed to create regex")?;
+
+    if directory.is_dir() {
+        for entry in std::fs::read_dir(&directory)
+            .with_context(|| format!("failed to read directory: {:?}", directory))?
+        {
+            let entry = entry.context("failed to get directory entry")?;
+
+            if let Ok(replica_info) = utils::parse_replica(&entry.path(), &re) {
+                replicas.push(replica_info);
+            }
+        }
+    }
+
+    log_debug!("local incremental replicas found: {:?}", replicas);
+
+    Ok(replicas)
+}
+
+/// Stores the chunks of `source` missing from `root` and writes the replica manifest.
+pub fn create_local_incremental_backup(
+    source: &Path,
+    root: &str,
+    replica_number: u32,
+) -> anyhow::Result<()> {
+    let root = Path::new(root);
+    let store = ChunkStore::new(root);
+    let timestamp = chrono::Utc::now().timestamp() as u64;
+
+    let mut written = 0;
+
+    let manifest = Manifest::build(source, timestamp, replica_number, |digest, data| {
+        if store.write(digest, data)? {
+            written += 1;
+        }
+
+        Ok(())
+    })?;
+
+    log_debug!(
+        "incremental backup stored {} new chunk(s) of {}",
+        written,
+        manifest.chunks().count()
+    );
+
+    manifest.save(
+        &root
+            .join(MANIFESTS_DIR)
+            .join(manifest_name(timestamp, replica_number)),
+    )
+}
+
+/// Reassembles a local incremental replica as `destination/<name>`.
+pub fn restore_local_incremental_replica(
+    replica: &BackupHookReplica,
+    root: &str,
+    destination: &Path,
+) -> anyhow::Result<PathBuf> {
+    let store = ChunkStore::new(Path::new(root));
+
+    Manifest::load(&replica.path)?.restore(destination, |digest| store.read(digest))
+}
+
+/// Removes the chunks no remaining manifest references.
+fn collect_garbage(root: &Path) -> anyhow::Result<usize> {
+    let store = ChunkStore::new(root);
+    let mut referenced = HashSet::new();
+
+    for replica in get_local_incremental_replicas(&root.to_string_lossy())? {
+        referenced.extend(Manifest::load(&replica.path)?.chunks().cloned());
+    }
+
+    let mut removed = 0;
+
+    for digest in store.digests()? {
+        if !referenced.contains(&digest) {
+            store.remove(&digest)?;
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+/// Removes the manifests not kept by the retention policy, then the orphaned chunks.
+///
+/// # Returns
+/// The pruned replicas, oldest first. Nothing is removed when `dry_run` is set.
+pub fn rotate_local_incremental_replicas(
+    local_replicas: Vec<BackupHookReplica>,
+    max_replicas: usize,
+    retention: &RetentionPolicy,
+    dry_run: bool,
+    root: &str,
+) -> anyhow::Result<Vec<BackupHookReplica>> {
+    let prunable = retention.select_prunable(
+        &local_replicas,
+        max_replicas,
+        chrono::Utc::now(),
+        |replica| Ok(Manifest::load(&replica.path)?.size()),
+    )?;
+
+    log_debug!(
+        "found {} old incremental replicas to remove",
+        prunable.len()
+    );
+
+    let pruned = take_replicas(local_replicas, &prunable);
+
+    if dry_run || pruned.is_empty() {
+        return Ok(pruned);
+    }
+
+    for old in &pruned {
+        std::fs::remove_file(&old.path)
+            .with_context(|| format!("failed to remove old manifest: {:?}", old.path))?;
+    }
+
+    let removed = collect_garbage(Path::new(root)).context("failed to remove orphaned chunks")?;
+
+    log_debug!("removed {} orphaned chunk(s)", removed);
+
+    Ok(pruned)
+}
@@ -0,0 +1,309 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Files are split in chunks of this size, so a change only stores the chunks it touched.
+pub const CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestKind {
+    File,
+    Directory,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Path relative to the backed up directory using `/`, empty for a single file.
+    pub path: String,
+    pub size: u64,
+    pub mode: Option<u32>,
+
+    /// Digests of the chunks holding the content, in order.
+    pub chunks: Vec<String>,
+}
+
+/// Describes how to reassemble one incremental replica from the chunk store.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: u32,
+    pub timestamp: u64,
+    pub replica_number: u32,
+
+    /// File name of the backed up file or directory.
+    pub name: String,
+    pub kind: ManifestKind,
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// File name of the manifest of a replica, e.g. `1700000000.3.json`.
+pub fn manifest_name(timestamp: u64, replica_number: u32) -> String {
+    format!("{}.{}.json", timestamp, replica_number)
+}
+
+/// Path of a chunk relative to the chunks directory, fanned out by digest prefix.
+pub fn chunk_path(digest: &str) -> String {
+    format!("{}/{}", &digest[..2.min(digest.len())], digest)
+}
+
+pub fn chunk_digest(data: &[u8]) -> String {
+    blake3::hash(data).to_hex().to_string()
+}
+
+#[cfg(unix)]
+fn mode(metadata: &std::fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn mode(_metadata: &std::fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: Option<u32>) -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Some(mode) = mode {
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+            .with_context(|| format!("failed to set permissions: {:?}", path))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: Option<u32>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+impl ManifestEntry {
+    fn build(
+        file: &Path,
+        relative_path: String,
+        store: &mut impl FnMut(&str, &[u8]) -> anyhow::Result<()>,
+    ) -> anyhow::Result<Self> {
+        let metadata = std::fs::metadata(file)
+            .with_context(|| format!("failed to read metadata: {:?}", file))?;
+
+        let mut reader = std::fs::File::open(file)
+            .with_context(|| format!("failed to open file: {:?}", file))?;
+
+        let mut chunks = Vec::new();
+        let mut size = 0u64;
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+
+        loop {
+            let mut filled = 0;
+
+            while filled < CHUNK_SIZE {
+                let read = reader
+                    .read(&mut buffer[filled..])
+                    .with_context(|| format!("failed to read file: {:?}", file))?;
+
+                if read == 0 {
+                    break;
+                }
+
+                filled += read;
+            }
+
+            if filled == 0 {
+                break;
+            }
+
+            let digest = chunk_digest(&buffer[..filled]);
+            store(&digest, &buffer[..filled])
+                .with_context(|| format!("failed to store chunk of {:?}", file))?;
+
+            chunks.push(digest);
+            size += filled as u64;
+
+            if filled < CHUNK_SIZE {
+                break;
+            }
+        }
+
+        Ok(Self {
+            path: relative_path,
+            size,
+            mode: mode(&metadata),
+            chunks,
+        })
+    }
+}
+
+impl Manifest {
+    /// Splits `source` into chunks and describes it.
+    ///
+    /// `store` receives every chunk with its digest and is expected to skip the
+    /// ones already stored, which is what shares unchanged content between replicas.
+    pub fn build(
+        source: &Path,
+        timestamp: u64,
+        replica_number: u32,
+        mut store: impl FnMut(&str, &[u8]) -> anyhow::Result<()>,
+    ) -> anyhow::Result<Self> {
+        let name = source
+            .file_name()
+            .with_context(|| format!("failed to get file name of {:?}", source))?
+            .to_string_lossy()
+            .to_string();
+
+        let (kind, entries) = match source.is_dir() {
+            true => {
+                let mut entries = Vec::new();
+
+                for entry in walkdir::WalkDir::new(source).sort_by_file_name() {
+                    let entry = entry.with_context(|| format!("failed to walk {:?}", source))?;
+
+                    if !entry.file_type().is_file() {
+                        continue;
+                    }
+
+                    let relative_path = entry
+                        .path()
+                        .strip_prefix(source)
+                        .context("failed to build relative path")?
+                        .components()
+                        .map(|c| c.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/");
+
+                    entries.push(ManifestEntry::build(
+                        entry.path(),
+                        relative_path,
+                        &mut store,
+                    )?);
+                }
+
+                (ManifestKind::Directory, entries)
+            }
+            false => (
+                ManifestKind::File,
+                vec![ManifestEntry::build(source, String::new(), &mut store)?],
+            ),
+        };
+
+        Ok(Self {
+            version: MANIFEST_VERSION,
+            timestamp,
+            replica_number,
+            name,
+            kind,
+            entries,
+        })
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read manifest: {:?}", path))?;
+
+        Self::parse(&content).with_context(|| format!("invalid manifest: {:?}", path))
+    }
+
+    pub fn parse(content: &str) -> anyhow::Result<Self> {
+        let manifest: Self = serde_json::from_str(content).context("failed to parse manifest")?;
+
+        anyhow::ensure!(
+            manifest.version <= MANIFEST_VERSION,
+            "unsupported manifest version: {}",
+            manifest.version
+        );
+
+        Ok(manifest)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create directory: {:?}", parent))?;
+        }
+
+        let content = serde_json::to_string(self).context("failed to serialize manifest")?;
+
+        // written aside and renamed, an interrupted save never leaves a truncated manifest
+        let temp_path = path.with_extension("tmp");
+
+        std::fs::write(&temp_path, content)
+            .with_context(|| format!("failed to write manifest: {:?}", temp_path))?;
+
+        std::fs::rename(&temp_path, path)
+            .with_context(|| format!("failed to store manifest: {:?}", path))
+    }
+
+    /// Total size of the backed up content.
+    pub fn size(&self) -> u64 {
+        self.entries.iter().map(|e| e.size).sum()
+    }
+
+    pub fn chunks(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter().flat_map(|e| &e.chunks)
+    }
+
+    /// Reassembles the replica as `destination/<name>`, reading chunks with `load`.
+    ///
+    /// Every chunk is verified against its digest before being written.
+    pub fn restore(
+        &self,
+        destination: &Path,
+        mut load: impl FnMut(&str) -> anyhow::Result<Vec<u8>>,
+    ) -> anyhow::Result<PathBuf> {
+        let root = destination.join(&self.name);
+
+        if self.kind == ManifestKind::Directory {
+            std::fs::create_dir_all(&root)
+                .with_context(|| format!("failed to create directory: {:?}", root))?;
+        }
+
+        for entry in &self.entries {
+            let relative_path = Path::new(&entry.path);
+
+            anyhow::ensure!(
+                relative_path
+                    .components()
+                    .all(|c| matches!(c, Component::Normal(_))),
+                "invalid path in manifest: {}",
+                entry.path
+            );
+
+            let file = match self.kind {
+                ManifestKind::File => root.clone(),
+                ManifestKind::Directory => root.join(relative_path),
+            };
+
+            if let Some(parent) = file.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to create directory: {:?}", parent))?;
+            }
+
+            let mut writer = std::fs::File::create(&file)
+                .with_context(|| format!("failed to create file: {:?}", file))?;
+
+            for digest in &entry.chunks {
+                let data = load(digest).with_context(|| format!("missing chunk: {}", digest))?;
+
+                anyhow::ensure!(
+                    chunk_digest(&data) == *digest,
+                    "chunk {} is corrupted",
+                    digest
+                );
+
+                writer
+                    .write_all(&data)
+                    .with_context(|| format!("failed to write file: {:?}", file))?;
+            }
+
+            set_mode(&file, entry.mode)?;
+        }
+
+        Ok(root)
+    }
+}
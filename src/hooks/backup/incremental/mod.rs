@@ -0,0 +1,15 @@
+pub mod local;
+pub mod manifest;
+pub mod remote;
+pub mod store;
+
+pub use super::incremental::{manifest::Manifest, store::ChunkStore};
+
+/// Directory holding the chunks shared by every incremental replica.
+pub const CHUNKS_DIR: &str = "chunks";
+
+/// Directory holding one manifest per incremental replica.
+pub const MANIFESTS_DIR: &str = "manifests";
+
+/// Matches manifest file names, e.g. `1700000000.3.json`.
+pub const MANIFEST_PATTERN: &str = r"^(\d+)\.(\d+)\.json$";
@@ -0,0 +1,359 @@
+use crate::{
+    config::prelude::{AppConfig, Remote},
+    hooks::{
+        artifacts::{self, DOWNLOAD_PREFIX, STAGING_PREFIX},
+        backup::{
+            backup_hook::BackupHookReplica,
+            incremental::{
+                CHUNKS_DIR, ChunkStore, MANIFEST_PATTERN, MANIFESTS_DIR, Manifest,
+                manifest::{chunk_path, manifest_name},
+            },
+            retention::{RetentionPolicy, take_replicas},
+            utils::{
+                self,
+                create_remote_backup::{RCLONE_DIRECTORY_NOT_FOUND, RCLONE_FILE_NOT_FOUND},
+            },
+        },
+    },
+    log_debug,
+};
+use anyhow::Context;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+fn remote_root(remote: &Remote, root: &str) -> String {
+    format!("{}:{}", remote.remote_name, root.trim_end_matches('/'))
+}
+
+fn run_rclone(rclone: &str, args: &[&str]) -> anyhow::Result<Vec<u8>> {
+    let output = std::process::Command::new(rclone)
+        .args(args)
+        .output()
+        .with_context(|| format!("failed to execute rclone {}", args[0]))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "rclone {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(output.stdout)
+}
+
+/// Lists the files under a remote directory, recursively if asked.
+///
+/// # Returns
+/// - `Ok(Some(stdout))` with one file per line.
+/// - `Ok(None)` if the directory does not exist yet.
+/// - `Err` if rclone failed for any other reason (e.g. authentication or network).
+fn list_files(rclone: &str, directory: &str, recursive: bool) -> anyhow::Result<Option<Vec<u8>>> {
+    let mut args = vec!["lsf", "--files-only", directory];
+
+    if recursive {
+        args.push("-R");
+    }
+
+    let output = std::process::Command::new(rclone)
+        .args(&args)
+        .output()
+        .context("failed to execute rclone lsf")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        return match output.status.code() {
+            Some(RCLONE_DIRECTORY_NOT_FOUND | RCLONE_FILE_NOT_FOUND) => Ok(None),
+            _ if stderr.contains("not found") => Ok(None),
+            _ => anyhow::bail!("failed to list {}: {}", directory, stderr.trim()),
+        };
+    }
+
+    Ok(Some(output.stdout))
+}
+
+fn path_str(path: &Path) -> anyhow::Result<&str> {
+    path.to_str()
+        .with_context(|| format!("failed to convert {:?} to str", path))
+}
+
+/// Writes `lines` to a file usable with rclone `--files-from`.
+fn files_from(dir: &Path, lines: &[String]) -> anyhow::Result<PathBuf> {
+    let path = dir.join("files-from.txt");
+
+    std::fs::write(&path, lines.join("\n"))
+        .with_context(|| format!("failed to write file list: {:?}", path))?;
+
+    Ok(path)
+}
+
+pub fn get_remote_incremental_replicas(
+    root: &str,
+    rclone: &str,
+    remote: &Remote,
+) -> anyhow::Result<Vec<BackupHookReplica>> {
+    let manifests = format!("{}/{}", remote_root(remote, root), MANIFESTS_DIR);
+
+    let Some(stdout) = list_files(rclone, &manifests, false)? else {
+        return Ok(Vec::new());
+    };
+
+    let re = regex::Regex::new(MANIFEST_PATTERN).context("failed to create regex")?;
+    let mut replicas = Vec::new();
+
+    for filename in String::from_utf8_lossy(&stdout).lines() {
+        let path = Path::new(MANIFESTS_DIR).join(filename);
+
+        if let Ok(replica_info) = utils::parse_replica(&path, &re) {
+            replicas.push(replica_info);
+        }
+    }
+
+    log_debug!("remote incremental replicas found: {:?}", replicas);
+
+    Ok(replicas)
+}
+
+/// Digests of the chunks stored on the remote, empty if there are none yet.
+fn remote_digests(root: &str, rclone: &str, remote: &Remote) -> anyhow::Result<HashSet<String>> {
+    let chunks = format!("{}/{}", remote_root(remote, root), CHUNKS_DIR);
+
+    let Some(stdout) = list_files(rclone, &chunks, true)? else {
+        log_debug!("no remote chunks yet: {}", chunks);
+        return Ok(HashSet::new());
+    };
+
+    Ok(String::from_utf8_lossy(&stdout)
+        .lines()
+        .filter_map(|line| line.rsplit('/').next())
+        .map(String::from)
+        .collect())
+}
+
+/// Uploads the chunks of `source` missing from the remote, then the replica manifest.
+///
+/// Chunks are uploaded first, so an interrupted backup never leaves a manifest
+/// referencing missing chunks.
+pub fn create_remote_incremental_backup(
+    source: &Path,
+    root: &str,
+    replica_number: u32,
+    rclone: &str,
+    remote: &Remote,
+    cfg: &AppConfig,
+) -> anyhow::Result<()> {
+    let known = remote_digests(root, rclone, remote).context("failed to list remote chunks")?;
+
+    let staging =
+        artifacts::create_dir(STAGING_PREFIX, cfg).context("failed to create staging directory")?;
+    let store = ChunkStore::new(staging.path());
+    let timestamp = chrono::Utc::now().timestamp() as u64;
+
+    let manifest = Manifest::build(source, timestamp, replica_number, |digest, data| {
+        if !known.contains(digest) {
+            store.write(digest, data)?;
+        }
+
+        Ok(())
+    })?;
+
+    let staged_chunks = staging.path().join(CHUNKS_DIR);
+    let destination = remote_root(remote, root);
+
+    if staged_chunks.is_dir() {
+        run_rclone(
+            rclone,
+            &[
+                "copy",
+                path_str(&staged_chunks)?,
+                &format!("{}/{}", destination, CHUNKS_DIR),
+            ],
+        )
+        .context("failed to upload chunks")?;
+    }
+
+    let name = manifest_name(timestamp, replica_number);
+    let manifest_path = staging.path().join(&name);
+    manifest.save(&manifest_path)?;
+
+    run_rclone(
+        rclone,
+        &[
+            "copyto",
+            path_str(&manifest_path)?,
+            &format!("{}/{}/{}", destination, MANIFESTS_DIR, name),
+        ],
+    )
+    .context("failed to upload manifest")?;
+
+    Ok(())
+}
+
+fn fetch_manifest(
+    replica: &BackupHookReplica,
+    root: &str,
+    rclone: &str,
+    remote: &Remote,
+) -> anyhow::Result<Manifest> {
+    let source = format!(
+        "{}/{}",
+        remote_root(remote, root),
+        replica.path.to_string_lossy().replace('\\', "/")
+    );
+
+    let content = run_rclone(rclone, &["cat", &source])
+        .with_context(|| format!("failed to download manifest: {}", source))?;
+
+    Manifest::parse(&String::from_utf8_lossy(&content))
+}
+
+/// Downloads the chunks of a remote incremental replica and reassembles it as `destination/<name>`.
+pub fn restore_remote_incremental_replica(
+    replica: &BackupHookReplica,
+    root: &str,
+    rclone: &str,
+    remote: &Remote,
+    destination: &Path,
+    cfg: &AppConfig,
+) -> anyhow::Result<PathBuf> {
+    let manifest = fetch_manifest(replica, root, rclone, remote)?;
+
+    let download =
+        artifacts::create_dir(DOWNLOAD_PREFIX, cfg).context("failed to create temp directory")?;
+    let store = ChunkStore::new(download.path());
+
+    let chunks: Vec<String> = manifest
+        .chunks()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .map(|digest| chunk_path(digest))
+        .collect();
+
+    if !chunks.is_empty() {
+        let list = files_from(download.path(), &chunks)?;
+
+        run_rclone(
+            rclone,
+            &[
+                "copy",
+                &format!("{}/{}", remote_root(remote, root), CHUNKS_DIR),
+                path_str(&download.path().join(CHUNKS_DIR))?,
+                "--files-from",
+                path_str(&list)?,
+            ],
+        )
+        .context("failed to download chunks")?;
+    }
+
+    manifest.restore(destination, |digest| store.read(digest))
+}
+
+/// Removes the remote chunks no remaining manifest references.
+///
+/// # Returns
+/// The number of chunks removed.
+pub fn collect_garbage(
+    root: &str,
+    rclone: &str,
+    remote: &Remote,
+    cfg: &AppConfig,
+) -> anyhow::Result<usize> {
+    let download =
+        artifacts::create_dir(DOWNLOAD_PREFIX, cfg).context("failed to create temp directory")?;
+    let manifests = download.path().join(MANIFESTS_DIR);
+
+    run_rclone(
+        rclone,
+        &[
+            "copy",
+            &format!("{}/{}", remote_root(remote, root), MANIFESTS_DIR),
+            path_str(&manifests)?,
+        ],
+    )
+    .context("failed to download manifests")?;
+
+    let mut referenced = HashSet::new();
+
+    for entry in std::fs::read_dir(&manifests)
+        .with_context(|| format!("failed to read directory: {:?}", manifests))?
+    {
+        let entry = entry.context("failed to get directory entry")?;
+        referenced.extend(Manifest::load(&entry.path())?.chunks().cloned());
+    }
+
+    let orphaned: Vec<String> = remote_digests(root, rclone, remote)
+        .context("failed to list remote chunks")?
+        .into_iter()
+        .filter(|digest| !referenced.contains(digest))
+        .map(|digest| chunk_path(&digest))
+        .collect();
+
+    if orphaned.is_empty() {
+        return Ok(0);
+    }
+
+    let list = files_from(download.path(), &orphaned)?;
+
+    run_rclone(
+        rclone,
+        &[
+            "delete",
+            &format!("{}/{}", remote_root(remote, root), CHUNKS_DIR),
+            "--files-from",
+            path_str(&list)?,
+        ],
+    )
+    .context("failed to delete orphaned chunks")?;
+
+    Ok(orphaned.len())
+}
+
+/// Removes the remote manifests not kept by the retention policy.
+///
+/// Their chunks stay until `collect_garbage` runs.
+///
+/// # Returns
+/// The pruned replicas, oldest first. Nothing is removed when `dry_run` is set.
+pub fn rotate_remote_incremental_replicas(
+    remote_replicas: Vec<BackupHookReplica>,
+    max_replicas: usize,
+    retention: &RetentionPolicy,
+    dry_run: bool,
+    rclone: &str,
+    remote: &Remote,
+    root: &str,
+) -> anyhow::Result<Vec<BackupHookReplica>> {
+    let prunable = retention.select_prunable(
+        &remote_replicas,
+        max_replicas,
+        chrono::Utc::now(),
+        |replica| Ok(fetch_manifest(replica, root, rclone, remote)?.size()),
+    )?;
+
+    log_debug!(
+        "found {} old incremental replicas to remove",
+        prunable.len()
+    );
+
+    let pruned = take_replicas(remote_replicas, &prunable);
+
+    if dry_run || pruned.is_empty() {
+        return Ok(pruned);
+    }
+
+    for old in &pruned {
+        let manifest = format!(
+            "{}/{}",
+            remote_root(remote, root),
+            old.path.to_string_lossy().replace('\\', "/")
+        );
+
+        run_rclone(rclone, &["deletefile", &manifest])
+            .with_context(|| format!("failed to remove old manifest: {}", manifest))?;
+    }
+
+    Ok(pruned)
+}
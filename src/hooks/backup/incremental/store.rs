@@ -0,0 +1,79 @@
+use crate::hooks::backup::incremental::{CHUNKS_DIR, manifest::chunk_path};
+use anyhow::Context;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+/// Content-addressed chunks stored under `<root>/chunks`.
+#[derive(Debug, Clone)]
+pub struct ChunkStore {
+    chunks_dir: PathBuf,
+}
+
+impl ChunkStore {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            chunks_dir: root.join(CHUNKS_DIR),
+        }
+    }
+
+    fn path(&self, digest: &str) -> PathBuf {
+        self.chunks_dir.join(chunk_path(digest))
+    }
+
+    /// Writes a chunk unless it is already stored, returning whether it was written.
+    pub fn write(&self, digest: &str, data: &[u8]) -> anyhow::Result<bool> {
+        let path = self.path(digest);
+
+        if path.is_file() {
+            return Ok(false);
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create chunk directory: {:?}", parent))?;
+        }
+
+        let temp_path = path.with_extension("tmp");
+
+        std::fs::write(&temp_path, data)
+            .with_context(|| format!("failed to write chunk: {:?}", temp_path))?;
+
+        std::fs::rename(&temp_path, &path)
+            .with_context(|| format!("failed to store chunk: {:?}", path))?;
+
+        Ok(true)
+    }
+
+    pub fn read(&self, digest: &str) -> anyhow::Result<Vec<u8>> {
+        let path = self.path(digest);
+
+        std::fs::read(&path).with_context(|| format!("failed to read chunk: {:?}", path))
+    }
+
+    /// Digests of every stored chunk.
+    pub fn digests(&self) -> anyhow::Result<HashSet<String>> {
+        let mut digests = HashSet::new();
+
+        if !self.chunks_dir.is_dir() {
+            return Ok(digests);
+        }
+
+        for entry in walkdir::WalkDir::new(&self.chunks_dir) {
+            let entry = entry.context("failed to walk chunks")?;
+
+            if entry.file_type().is_file() && entry.path().extension().is_none() {
+                digests.insert(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+
+        Ok(digests)
+    }
+
+    pub fn remove(&self, digest: &str) -> anyhow::Result<()> {
+        let path = self.path(digest);
+
+        std::fs::remove_file(&path).with_context(|| format!("failed to remove chunk: {:?}", path))
+    }
+}
@@ -1,6 +1,8 @@
 pub mod backup_hook;
 pub mod config;
 pub mod hook;
-mod utils;
+pub mod incremental;
+pub mod retention;
+pub mod utils;
 
-pub use super::backup::hook::{BackupHook, BackupHookConfig, BackupType};
+pub use super::backup::hook::{BackupHook, BackupHookConfig, BackupType, SnapshotMode};
@@ -0,0 +1,195 @@
+use crate::{hooks::backup::backup_hook::BackupHookReplica, utils::schedule};
+use anyhow::Context;
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Grandfather-father-son retention applied on top of the replica count.
+///
+/// A replica is kept if it is one of the newest `replicas`, or the newest one of
+/// one of the latest `keep_hourly` hours, `keep_daily` days, `keep_weekly` ISO
+/// weeks or `keep_monthly` months. Kept replicas older than `max_age` are then
+/// dropped, and the oldest ones are dropped until the total fits in `max_size`.
+/// The newest replica is never pruned.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    #[serde(default)]
+    pub keep_hourly: u32,
+
+    #[serde(default)]
+    pub keep_daily: u32,
+
+    #[serde(default)]
+    pub keep_weekly: u32,
+
+    #[serde(default)]
+    pub keep_monthly: u32,
+
+    /// Interval such as `30d` or `12w`.
+    #[serde(default)]
+    pub max_age: Option<String>,
+
+    /// Size such as `500M` or `10G`. Files hardlinked between local replicas count
+    /// once, reflinked copies count at their apparent size.
+    #[serde(default)]
+    pub max_size: Option<String>,
+}
+
+impl std::fmt::Display for RetentionPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+
+        for (name, count) in [
+            ("hourly", self.keep_hourly),
+            ("daily", self.keep_daily),
+            ("weekly", self.keep_weekly),
+            ("monthly", self.keep_monthly),
+        ] {
+            if count > 0 {
+                parts.push(format!("{}: {}", name, count));
+            }
+        }
+
+        if let Some(max_age) = &self.max_age {
+            parts.push(format!("max_age: {}", max_age));
+        }
+
+        if let Some(max_size) = &self.max_size {
+            parts.push(format!("max_size: {}", max_size));
+        }
+
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Parses a size such as `1024`, `500K`, `500M`, `10G` or `1T` (powers of 1024).
+pub fn parse_size(value: &str) -> anyhow::Result<u64> {
+    let value = value.trim();
+    let upper = value.to_ascii_uppercase();
+    let number = upper.trim_end_matches(['B', 'I']);
+
+    let (digits, multiplier) = match number.chars().last() {
+        Some('K') => (&number[..number.len() - 1], 1u64 << 10),
+        Some('M') => (&number[..number.len() - 1], 1u64 << 20),
+        Some('G') => (&number[..number.len() - 1], 1u64 << 30),
+        Some('T') => (&number[..number.len() - 1], 1u64 << 40),
+        _ => (number, 1),
+    };
+
+    digits
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .with_context(|| format!("invalid size: {}", value))
+}
+
+impl RetentionPolicy {
+    /// Fails if `max_age` or `max_size` cannot be parsed.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(max_age) = &self.max_age {
+            schedule::parse_interval(max_age)?;
+        }
+
+        if let Some(max_size) = &self.max_size {
+            parse_size(max_size)?;
+        }
+
+        Ok(())
+    }
+
+    /// Selects the replicas to prune.
+    ///
+    /// # Parameters
+    /// - `replicas`: Replicas in any order.
+    /// - `keep_last`: Number of newest replicas always kept (the hook `replicas` count).
+    /// - `now`: Reference time for `max_age`.
+    /// - `size_of`: Size of a replica, only called when `max_size` is set, newest first.
+    ///
+    /// # Returns
+    /// Indices into `replicas` of the replicas to prune, oldest first.
+    pub fn select_prunable(
+        &self,
+        replicas: &[BackupHookReplica],
+        keep_last: usize,
+        now: DateTime<Utc>,
+        mut size_of: impl FnMut(&BackupHookReplica) -> anyhow::Result<u64>,
+    ) -> anyhow::Result<Vec<usize>> {
+        let mut order: Vec<usize> = (0..replicas.len()).collect();
+        order.sort_by_key(|&i| {
+            std::cmp::Reverse((replicas[i].timestamp, replicas[i].replica_number))
+        });
+
+        let mut kept: HashSet<usize> = order.iter().take(keep_last.max(1)).copied().collect();
+
+        for (count, format) in [
+            (self.keep_hourly, "%Y-%m-%d %H"),
+            (self.keep_daily, "%Y-%m-%d"),
+            (self.keep_weekly, "%G-W%V"),
+            (self.keep_monthly, "%Y-%m"),
+        ] {
+            let mut buckets = HashSet::new();
+
+            for &i in &order {
+                if buckets.len() >= count as usize {
+                    break;
+                }
+
+                let Some(date) = DateTime::from_timestamp(replicas[i].timestamp as i64, 0) else {
+                    continue;
+                };
+
+                if buckets.insert(date.with_timezone(&Local).format(format).to_string()) {
+                    kept.insert(i);
+                }
+            }
+        }
+
+        let newest = order.first().copied();
+
+        if let Some(max_age) = &self.max_age {
+            let cutoff = now
+                .checked_sub_signed(schedule::parse_interval(max_age)?)
+                .with_context(|| format!("max age is too large: {}", max_age))?
+                .timestamp();
+
+            kept.retain(|&i| Some(i) == newest || (replicas[i].timestamp as i64) >= cutoff);
+        }
+
+        if let Some(max_size) = &self.max_size {
+            let budget = parse_size(max_size)?;
+            let mut total = 0u64;
+
+            for &i in &order {
+                if !kept.contains(&i) {
+                    continue;
+                }
+
+                total = total.saturating_add(size_of(&replicas[i])?);
+
+                if total > budget && Some(i) != newest {
+                    kept.remove(&i);
+                }
+            }
+        }
+
+        Ok(order
+            .into_iter()
+            .rev()
+            .filter(|i| !kept.contains(i))
+            .collect())
+    }
+}
+
+/// Moves the replicas at `indices` out of `replicas`, in the order of `indices`.
+pub fn take_replicas(
+    replicas: Vec<BackupHookReplica>,
+    indices: &[usize],
+) -> Vec<BackupHookReplica> {
+    let mut replicas: Vec<Option<BackupHookReplica>> = replicas.into_iter().map(Some).collect();
+
+    indices
+        .iter()
+        .filter_map(|&index| replicas.get_mut(index).and_then(Option::take))
+        .collect()
+}
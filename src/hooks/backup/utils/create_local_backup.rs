@@ -1,10 +1,22 @@
-use crate::{hooks::prelude::HookContext, log_debug};
+use crate::{
+    hooks::{
+        backup::{SnapshotMode, backup_hook::BackupHookReplica, utils::snapshot::Snapshot},
+        prelude::HookContext,
+    },
+    log_debug,
+};
 use anyhow::Context;
 
+/// Writes `ctx.path` into `local_path/<timestamp>.<replica_number>`.
+///
+/// Directories are written inside the replica under their own name. `previous`
+/// is the latest replica, used by hardlink snapshots to link unchanged files.
 pub fn create_local_backup(
     ctx: &HookContext,
     local_path: &str,
     replica_number: u32,
+    snapshot: SnapshotMode,
+    previous: Option<&BackupHookReplica>,
 ) -> anyhow::Result<()> {
     let directory = std::path::Path::new(local_path);
 
@@ -17,20 +29,35 @@ pub fn create_local_backup(
 
     let backup_path = directory.join(format!("{}.{}", timestamp, replica_number));
 
-    log_debug!("writing files to backup path: {:?}", backup_path);
+    log_debug!(
+        "writing files to backup path: {:?} ({})",
+        backup_path,
+        snapshot
+    );
 
     if ctx.path.is_file() {
-        std::fs::copy(&ctx.path, &backup_path)
+        let previous = previous.map(|p| p.path.clone());
+
+        Snapshot::new(snapshot, previous.as_deref())
+            .create(&ctx.path, &backup_path)
             .with_context(|| format!("failed to copy file to backup: {:?}", backup_path))?;
 
         return Ok(());
     }
 
     if ctx.path.is_dir() {
+        let name = ctx
+            .path
+            .file_name()
+            .with_context(|| format!("failed to get directory name: {:?}", ctx.path))?;
+
         std::fs::create_dir_all(&backup_path)
             .with_context(|| format!("failed to create backup directory: {:?}", backup_path))?;
 
-        fs_extra::dir::copy(&ctx.path, &backup_path, &fs_extra::dir::CopyOptions::new())
+        let previous = previous.map(|p| p.path.join(name));
+
+        Snapshot::new(snapshot, previous.as_deref())
+            .create(&ctx.path, &backup_path.join(name))
             .with_context(|| format!("failed to copy directory to backup: {:?}", backup_path))?;
 
         return Ok(());
@@ -1,36 +1,126 @@
-use crate::{hooks::prelude::HookContext, log_debug};
+use crate::{
+    config::prelude::HookConfig, hooks::prelude::HookContext, log_debug, log_info, log_warn,
+};
 use anyhow::Context;
 
+/// rclone exit codes for a missing directory and a missing file.
+pub const RCLONE_DIRECTORY_NOT_FOUND: i32 = 3;
+pub const RCLONE_FILE_NOT_FOUND: i32 = 4;
+
+/// Looks up a remote object.
+///
+/// # Returns
+/// - `Ok(Some(is_dir))` if the object exists.
+/// - `Ok(None)` if it does not exist.
+/// - `Err` if rclone failed for any other reason (e.g. authentication or network).
+fn remote_stat(rclone_path: &str, target: &str) -> anyhow::Result<Option<bool>> {
+    let output = std::process::Command::new(rclone_path)
+        .args(["lsjson", "--stat", target])
+        .output()
+        .context("failed to execute rclone lsjson")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        return match output.status.code() {
+            Some(RCLONE_DIRECTORY_NOT_FOUND | RCLONE_FILE_NOT_FOUND) => Ok(None),
+            _ if stderr.contains("not found") => Ok(None),
+            _ => anyhow::bail!("failed to stat {}: {}", target, stderr.trim()),
+        };
+    }
+
+    let stat: serde_json::Value =
+        serde_json::from_slice(&output.stdout).context("failed to parse rclone lsjson output")?;
+
+    Ok(Some(
+        stat.get("IsDir").and_then(|v| v.as_bool()).unwrap_or(false),
+    ))
+}
+
+/// Whether the remote can copy objects server-side, without downloading them.
+fn supports_server_side_copy(rclone_path: &str, remote_name: &str) -> bool {
+    let output = std::process::Command::new(rclone_path)
+        .args(["backend", "features", &format!("{}:", remote_name)])
+        .output();
+
+    let output = match output {
+        Ok(output) if output.status.success() => output,
+        _ => return false,
+    };
+
+    serde_json::from_slice::<serde_json::Value>(&output.stdout)
+        .ok()
+        .and_then(|features| features.pointer("/Features/Copy").and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
+
+/// Copies the remote content of the path into `remote_path/<timestamp>.<replica_number>`.
+///
+/// The copied object is resolved from `hooks` like the sync does, so renamed
+/// artifacts (e.g. `name.zip`) are backed up instead of their parent directory.
+/// The object is kept under its own name inside the replica directory, files and
+/// directories alike, so restores can tell the content from the replica itself.
+///
+/// # Returns
+/// - `Ok(true)` if a replica was created.
+/// - `Ok(false)` if there was nothing to back up yet.
 pub fn create_remote_backup(
     ctx: &HookContext,
+    hooks: &[HookConfig],
     remote_path: &str,
     replica_number: u32,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<bool> {
+    let remote_name = &ctx.remote_config.remote_name;
+    let object = ctx.path_config.remote_object(hooks);
+    let source = format!("{}:{}", remote_name, object);
+
+    let Some(is_dir) = remote_stat(&ctx.rclone_path, &source)? else {
+        log_info!("nothing to back up, remote source not found: {}", source);
+        return Ok(false);
+    };
+
+    if !supports_server_side_copy(&ctx.rclone_path, remote_name) {
+        log_warn!(
+            "remote {} does not support server-side copy, the backup will be downloaded and uploaded again",
+            remote_name
+        );
+    }
+
     let timestamp = chrono::Utc::now().timestamp();
+    let replica = format!(
+        "{}:{}/{}.{}",
+        remote_name,
+        remote_path.trim_end_matches('/'),
+        timestamp,
+        replica_number
+    );
+
+    let filename = std::path::Path::new(&object)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .context("failed to get remote file name")?;
+
+    let destination = format!("{}/{}", replica, filename);
+    let command = match is_dir {
+        true => "copy",
+        false => "copyto",
+    };
 
-    log_debug!("creating remote backup");
+    log_debug!("creating remote backup: {} -> {}", source, destination);
 
     let output = std::process::Command::new(&ctx.rclone_path)
-        .args([
-            "copyto",
-            &format!(
-                "{}:{}",
-                ctx.remote_config.remote_name, ctx.path_config.remote_path
-            ),
-            &format!(
-                "{}:{}/{}",
-                ctx.remote_config.remote_name,
-                remote_path,
-                &format!("{}.{}", timestamp, replica_number)
-            ),
-        ])
+        .args([command, &source, &destination])
         .output()
         .context("failed to execute backup in remote")?;
 
     if !output.status.success() {
-        log_debug!("remote source not found, skipping remote backup");
-        return Ok(());
+        anyhow::bail!(
+            "failed to copy {} to {}: {}",
+            source,
+            destination,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
     }
 
-    Ok(())
+    Ok(true)
 }
@@ -5,6 +5,7 @@ pub mod get_remote_replicas;
 pub mod parse_replica;
 pub mod rotate_local_replicas;
 pub mod rotate_remote_replicas;
+pub mod snapshot;
 
 pub use super::utils::{
     create_local_backup::create_local_backup, create_remote_backup::create_remote_backup,
@@ -1,33 +1,90 @@
-use crate::hooks::backup::backup_hook::BackupHookReplica;
+use crate::{
+    hooks::backup::{
+        backup_hook::BackupHookReplica,
+        retention::{RetentionPolicy, take_replicas},
+    },
+    log_debug,
+};
 use anyhow::Context;
+use std::collections::HashSet;
 
+#[cfg(unix)]
+fn file_id(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Size of the data a replica adds to the replicas already measured.
+///
+/// Hardlinked snapshots share files with other replicas, each file (device, inode)
+/// is only counted once across the calls sharing `seen`. Reflinked copies have
+/// their own inodes and are counted at their apparent size.
+fn local_replica_size(
+    replica: &BackupHookReplica,
+    seen: &mut HashSet<(u64, u64)>,
+) -> anyhow::Result<u64> {
+    let mut size = 0;
+
+    for entry in walkdir::WalkDir::new(&replica.path) {
+        let entry = entry.with_context(|| format!("failed to walk replica: {:?}", replica.path))?;
+
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let metadata = entry
+            .metadata()
+            .with_context(|| format!("failed to read metadata: {:?}", entry.path()))?;
+
+        if file_id(&metadata).is_none_or(|id| seen.insert(id)) {
+            size += metadata.len();
+        }
+    }
+
+    Ok(size)
+}
+
+/// Removes the local replicas not kept by the retention policy.
+///
+/// # Returns
+/// The pruned replicas, oldest first. Nothing is removed when `dry_run` is set.
 pub fn rotate_local_replicas(
-    local_replicas: &mut [BackupHookReplica],
+    local_replicas: Vec<BackupHookReplica>,
     max_replicas: usize,
-) -> anyhow::Result<()> {
-    local_replicas.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    retention: &RetentionPolicy,
+    dry_run: bool,
+) -> anyhow::Result<Vec<BackupHookReplica>> {
+    let mut seen = HashSet::new();
+    let prunable = retention.select_prunable(
+        &local_replicas,
+        max_replicas,
+        chrono::Utc::now(),
+        |replica| local_replica_size(replica, &mut seen),
+    )?;
 
-    let current_count = local_replicas.len();
+    log_debug!("found {} old replicas to remove", prunable.len());
 
-    if current_count >= max_replicas {
-        let to_remove = (current_count - max_replicas) + 1;
+    let mut pruned = Vec::with_capacity(prunable.len());
 
-        for old in local_replicas.iter().rev().take(to_remove) {
+    for old in take_replicas(local_replicas, &prunable) {
+        if !dry_run {
             if old.path.is_dir() {
                 std::fs::remove_dir_all(&old.path)
                     .with_context(|| format!("failed to remove old replica {:?}", old.path))?;
-
-                continue;
-            }
-
-            if old.path.is_file() {
+            } else if old.path.is_file() {
                 std::fs::remove_file(&old.path)
                     .with_context(|| format!("failed to remove old replica: {:?}", old.path))?;
-
-                continue;
             }
         }
+
+        pruned.push(old);
     }
 
-    Ok(())
+    Ok(pruned)
 }
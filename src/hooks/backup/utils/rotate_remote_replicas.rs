@@ -1,35 +1,84 @@
 use crate::{
-    config::prelude::Remote, hooks::backup::backup_hook::BackupHookReplica, log_debug, log_warn,
+    config::prelude::Remote,
+    hooks::backup::{
+        backup_hook::BackupHookReplica,
+        retention::{RetentionPolicy, take_replicas},
+    },
+    log_debug, log_warn,
 };
 use anyhow::Context;
 
+fn replica_remote_path(
+    replica: &BackupHookReplica,
+    remote_config: &Remote,
+    remote_backup_path: &str,
+) -> String {
+    let filename = replica
+        .path
+        .file_name()
+        .unwrap_or(replica.path.as_os_str())
+        .to_string_lossy();
+
+    format!(
+        "{}:{}/{}",
+        remote_config.remote_name, remote_backup_path, filename
+    )
+}
+
+fn remote_replica_size(rclone_path: &str, remote_path: &str) -> anyhow::Result<u64> {
+    let output = std::process::Command::new(rclone_path)
+        .args(["size", "--json", remote_path])
+        .output()
+        .with_context(|| format!("failed to get size of remote replica: {}", remote_path))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to get size of remote replica: {} ({})",
+            remote_path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let size: serde_json::Value =
+        serde_json::from_slice(&output.stdout).context("failed to parse rclone size output")?;
+
+    size.get("bytes")
+        .and_then(|bytes| bytes.as_u64())
+        .context("rclone size output has no bytes")
+}
+
+/// Purges the remote replicas not kept by the retention policy.
+///
+/// # Returns
+/// The pruned replicas, oldest first. Nothing is purged when `dry_run` is set.
 pub fn rotate_remote_replicas(
-    remote_replicas: &mut [BackupHookReplica],
+    remote_replicas: Vec<BackupHookReplica>,
     max_replicas: usize,
+    retention: &RetentionPolicy,
+    dry_run: bool,
     rclone_path: &str,
     remote_config: &Remote,
     remote_backup_path: &str,
-) -> anyhow::Result<()> {
-    remote_replicas.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-
-    let current_count = remote_replicas.len();
+) -> anyhow::Result<Vec<BackupHookReplica>> {
+    let prunable = retention.select_prunable(
+        &remote_replicas,
+        max_replicas,
+        chrono::Utc::now(),
+        |replica| {
+            remote_replica_size(
+                rclone_path,
+                &replica_remote_path(replica, remote_config, remote_backup_path),
+            )
+        },
+    )?;
 
-    if current_count >= max_replicas {
-        let to_remove = (current_count - max_replicas) + 1;
+    log_debug!("found {} old replicas to remove", prunable.len());
 
-        log_debug!("found {} old replicas to remove", to_remove);
+    let mut pruned = Vec::with_capacity(prunable.len());
 
-        for old in remote_replicas.iter().rev().take(to_remove) {
-            let filename = old
-                .path
-                .file_name()
-                .unwrap_or(old.path.as_os_str())
-                .to_string_lossy();
-
-            let remote_path = format!(
-                "{}:{}/{}",
-                remote_config.remote_name, remote_backup_path, filename
-            );
+    for old in take_replicas(remote_replicas, &prunable) {
+        if !dry_run {
+            let remote_path = replica_remote_path(&old, remote_config, remote_backup_path);
 
             log_debug!("removing old replica: {}", remote_path);
 
@@ -45,10 +94,14 @@ pub fn rotate_remote_replicas(
                     "failed to purge remote file/dir: {} ({})",
                     remote_path,
                     stderr
-                )
+                );
+
+                continue;
             }
         }
+
+        pruned.push(old);
     }
 
-    Ok(())
+    Ok(pruned)
 }
@@ -0,0 +1,145 @@
+use crate::{hooks::backup::SnapshotMode, log_debug};
+use anyhow::Context;
+use std::path::Path;
+
+/// Writes a local replica with the configured snapshot mode.
+///
+/// - `Copy` copies every file.
+/// - `Reflink` clones files (btrfs, XFS, APFS...) and copies when unsupported.
+/// - `Hardlink` links files unchanged since `previous` (same size and mtime),
+///   like rsync `--link-dest`, and copies the others preserving their mtime.
+/// - `Auto` uses reflinks when the filesystem supports them, hardlinks otherwise.
+pub struct Snapshot<'a> {
+    mode: SnapshotMode,
+
+    /// Previous replica of the same content, laid out like the one being written.
+    previous: Option<&'a Path>,
+
+    reflink_supported: Option<bool>,
+}
+
+impl<'a> Snapshot<'a> {
+    pub fn new(mode: SnapshotMode, previous: Option<&'a Path>) -> Self {
+        Self {
+            mode,
+            previous,
+            reflink_supported: None,
+        }
+    }
+
+    fn copy(source: &Path, target: &Path, preserve_mtime: bool) -> anyhow::Result<()> {
+        std::fs::copy(source, target)
+            .with_context(|| format!("failed to copy {:?} to {:?}", source, target))?;
+
+        if preserve_mtime {
+            let modified = std::fs::metadata(source)
+                .and_then(|m| m.modified())
+                .with_context(|| format!("failed to read mtime: {:?}", source))?;
+
+            std::fs::File::options()
+                .write(true)
+                .open(target)
+                .and_then(|f| f.set_modified(modified))
+                .with_context(|| format!("failed to set mtime: {:?}", target))?;
+        }
+
+        Ok(())
+    }
+
+    fn reflink(&mut self, source: &Path, target: &Path) -> bool {
+        if self.reflink_supported == Some(false) {
+            return false;
+        }
+
+        match reflink_copy::reflink(source, target) {
+            Ok(()) => {
+                self.reflink_supported = Some(true);
+                true
+            }
+            Err(e) => {
+                log_debug!("reflink not supported, falling back: {}", e);
+                self.reflink_supported = Some(false);
+                false
+            }
+        }
+    }
+
+    /// Links `target` to the previous replica of the file if it did not change.
+    fn hardlink(&self, source: &Path, target: &Path, relative: &Path) -> anyhow::Result<bool> {
+        let Some(previous) = self
+            .previous
+            .map(|p| match relative.as_os_str().is_empty() {
+                true => p.to_path_buf(),
+                false => p.join(relative),
+            })
+        else {
+            return Ok(false);
+        };
+
+        let (Ok(current), Ok(old)) = (std::fs::metadata(source), std::fs::metadata(&previous))
+        else {
+            return Ok(false);
+        };
+
+        let unchanged = old.is_file()
+            && current.len() == old.len()
+            && matches!((current.modified(), old.modified()), (Ok(a), Ok(b)) if a == b);
+
+        if !unchanged {
+            return Ok(false);
+        }
+
+        std::fs::hard_link(&previous, target)
+            .with_context(|| format!("failed to link {:?} to {:?}", previous, target))?;
+
+        Ok(true)
+    }
+
+    fn file(&mut self, source: &Path, target: &Path, relative: &Path) -> anyhow::Result<()> {
+        match self.mode {
+            SnapshotMode::Copy => Self::copy(source, target, false),
+            SnapshotMode::Reflink => match self.reflink(source, target) {
+                true => Ok(()),
+                false => Self::copy(source, target, false),
+            },
+            SnapshotMode::Hardlink => match self.hardlink(source, target, relative)? {
+                true => Ok(()),
+                false => Self::copy(source, target, true),
+            },
+            SnapshotMode::Auto => {
+                if self.reflink(source, target) || self.hardlink(source, target, relative)? {
+                    return Ok(());
+                }
+
+                Self::copy(source, target, true)
+            }
+        }
+    }
+
+    /// Writes `source` to `target`, recursing into directories.
+    pub fn create(&mut self, source: &Path, target: &Path) -> anyhow::Result<()> {
+        if !source.is_dir() {
+            return self.file(source, target, Path::new(""));
+        }
+
+        for entry in walkdir::WalkDir::new(source) {
+            let entry = entry.with_context(|| format!("failed to walk {:?}", source))?;
+
+            let relative = entry
+                .path()
+                .strip_prefix(source)
+                .context("failed to build relative path")?;
+
+            let destination = target.join(relative);
+
+            if entry.path().is_dir() {
+                std::fs::create_dir_all(&destination)
+                    .with_context(|| format!("failed to create directory: {:?}", destination))?;
+            } else if entry.path().is_file() {
+                self.file(entry.path(), &destination, relative)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{
+    config::prelude::{HookConfig, HookExecType, Hooks},
+    hooks::{backup::retention::parse_size, chunk::ChunkHookConfig},
+    log_info,
+};
+use anyhow::Context;
+use inquire::{Text, validator::Validation};
+
+impl ChunkHookConfig {
+    pub fn build(exec_type: HookExecType) -> anyhow::Result<HookConfig> {
+        log_info!("configuring {} for {}", Hooks::Chunk, exec_type);
+
+        let part_size = match exec_type {
+            HookExecType::Push => Some(
+                Text::new("Part size:")
+                    .with_default("100M")
+                    .with_help_message("e.g. 500K, 100M, 2G. place it after the Zip hook")
+                    .with_validator(|size: &str| {
+                        Ok(match parse_size(size) {
+                            Ok(size) if size > 0 => Validation::Valid,
+                            _ => Validation::Invalid("invalid size".into()),
+                        })
+                    })
+                    .prompt()
+                    .context("failed to get part size")?
+                    .trim()
+                    .to_string(),
+            ),
+            HookExecType::Pull => None,
+        };
+
+        Ok(HookConfig::Chunk(Self {
+            exec: exec_type,
+            part_size,
+            when: None,
+        }))
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{
+    config::prelude::{AppConfig, Hook, HookExecType},
+    define_hook,
+    hooks::{
+        artifacts::{self, ArtifactDir},
+        backup::retention::parse_size,
+        chunk::index::{ChunkIndex, ChunkPart, INDEX_VERSION, PARTS_EXTENSION, part_name},
+        prelude::HookContext,
+    },
+    log_debug, log_info,
+};
+use anyhow::Context;
+use std::{
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+pub const CHUNK_PREFIX: &str = "rcloud-chunk-";
+
+/// Size of the buffer used to stream the artifact.
+const BUFFER_SIZE: usize = 1024 * 1024;
+
+define_hook!(ChunkHook {
+    part_size: Option<String>,
+});
+
+fn temp_dir(cfg: &AppConfig) -> anyhow::Result<ArtifactDir> {
+    artifacts::create_dir(CHUNK_PREFIX, cfg)
+}
+
+/// Copies up to `limit` bytes from `reader` to `writer`, feeding every hasher.
+///
+/// # Returns
+/// The number of bytes copied.
+fn copy_hashed(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    limit: u64,
+    hashers: &mut [&mut blake3::Hasher],
+) -> anyhow::Result<u64> {
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    let mut copied = 0u64;
+
+    while copied < limit {
+        let wanted = (limit - copied).min(BUFFER_SIZE as u64) as usize;
+        let read = reader
+            .read(&mut buffer[..wanted])
+            .context("failed to read")?;
+
+        if read == 0 {
+            break;
+        }
+
+        writer
+            .write_all(&buffer[..read])
+            .context("failed to write")?;
+
+        for hasher in hashers.iter_mut() {
+            hasher.update(&buffer[..read]);
+        }
+
+        copied += read as u64;
+    }
+
+    Ok(copied)
+}
+
+impl ChunkHook {
+    pub fn part_size(&self) -> anyhow::Result<u64> {
+        let part_size = self
+            .part_size
+            .as_deref()
+            .context("part size is required to split files")?;
+
+        let size = parse_size(part_size)?;
+        anyhow::ensure!(size > 0, "part size must be greater than 0");
+
+        Ok(size)
+    }
+
+    /// Splits `source` into numbered parts and their index inside `destination`.
+    pub fn split(source: &Path, destination: &Path, part_size: u64) -> anyhow::Result<ChunkIndex> {
+        std::fs::create_dir_all(destination)
+            .with_context(|| format!("failed to create directory: {:?}", destination))?;
+
+        let name = source
+            .file_name()
+            .with_context(|| format!("failed to get file name of {:?}", source))?
+            .to_string_lossy()
+            .to_string();
+
+        let mut reader = std::io::BufReader::new(
+            std::fs::File::open(source)
+                .with_context(|| format!("failed to open file: {:?}", source))?,
+        );
+
+        let mut total = blake3::Hasher::new();
+        let mut parts = Vec::new();
+        let mut size = 0;
+
+        loop {
+            let file = part_name(parts.len() + 1);
+            let path = destination.join(&file);
+
+            let mut writer = std::fs::File::create(&path)
+                .with_context(|| format!("failed to create part: {:?}", path))?;
+
+            let mut hasher = blake3::Hasher::new();
+            let copied = copy_hashed(
+                &mut reader,
+                &mut writer,
+                part_size,
+                &mut [&mut hasher, &mut total],
+            )
+            .with_context(|| format!("failed to write part: {:?}", path))?;
+
+            if copied == 0 {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("failed to remove empty part: {:?}", path))?;
+                break;
+            }
+
+            size += copied;
+            parts.push(ChunkPart {
+                file,
+                size: copied,
+                digest: hasher.finalize().to_hex().to_string(),
+            });
+
+            if copied < part_size {
+                break;
+            }
+        }
+
+        let index = ChunkIndex {
+            version: INDEX_VERSION,
+            name,
+            size,
+            digest: total.finalize().to_hex().to_string(),
+            part_size,
+            parts,
+        };
+
+        index.save(destination)?;
+
+        Ok(index)
+    }
+
+    /// Reassembles the parts listed in the index of `source` as `destination/<name>`.
+    ///
+    /// Every part is checked against its size and digest, then the whole file.
+    /// Parts not listed in the index (e.g. left by a previous, larger push) are ignored.
+    pub fn join(source: &Path, destination: &Path) -> anyhow::Result<PathBuf> {
+        let index = ChunkIndex::load(source)?;
+
+        anyhow::ensure!(
+            Path::new(&index.name).file_name() == Some(index.name.as_ref()),
+            "invalid file name in chunk index: {}",
+            index.name
+        );
+
+        let output = destination.join(&index.name);
+        let mut writer = std::fs::File::create(&output)
+            .with_context(|| format!("failed to create file: {:?}", output))?;
+
+        let mut total = blake3::Hasher::new();
+
+        for part in &index.parts {
+            anyhow::ensure!(
+                Path::new(&part.file).file_name() == Some(part.file.as_ref()),
+                "invalid part name in chunk index: {}",
+                part.file
+            );
+
+            let path = source.join(&part.file);
+            let mut reader =
+                std::fs::File::open(&path).with_context(|| format!("missing part: {:?}", path))?;
+
+            let mut hasher = blake3::Hasher::new();
+            let copied = copy_hashed(
+                &mut reader,
+                &mut writer,
+                u64::MAX,
+                &mut [&mut hasher, &mut total],
+            )
+            .with_context(|| format!("failed to read part: {:?}", path))?;
+
+            anyhow::ensure!(
+                copied == part.size && hasher.finalize().to_hex().as_str() == part.digest,
+                "part {} is corrupted",
+                part.file
+            );
+        }
+
+        anyhow::ensure!(
+            total.finalize().to_hex().as_str() == index.digest,
+            "reassembled {} does not match its digest",
+            index.name
+        );
+
+        Ok(output)
+    }
+}
+
+impl Hook for ChunkHook {
+    fn process(&self, ctx: HookContext, cfg: &AppConfig) -> anyhow::Result<HookContext> {
+        anyhow::ensure!(
+            ctx.file_exists(),
+            "source file does not exists: {:?}",
+            &ctx.path
+        );
+
+        match self.exec {
+            HookExecType::Push => {
+                anyhow::ensure!(
+                    ctx.path.is_file(),
+                    "chunk hook splits a single file, add a Zip hook before it: {:?}",
+                    ctx.path
+                );
+
+                let name = ctx
+                    .path
+                    .file_name()
+                    .with_context(|| format!("failed to get file name of {:?}", ctx.path))?
+                    .to_string_lossy()
+                    .to_string();
+
+                let temp_dir = temp_dir(cfg)?;
+                let destination = temp_dir
+                    .path()
+                    .join(format!("{}.{}", name, PARTS_EXTENSION));
+
+                let index = Self::split(&ctx.path, &destination, self.part_size()?)?;
+                let _ = temp_dir.keep();
+
+                log_info!(
+                    "split {} ({} bytes) into {} part(s)",
+                    name,
+                    index.size,
+                    index.parts.len()
+                );
+
+                Ok(HookContext {
+                    path: destination,
+                    ..ctx
+                })
+            }
+            HookExecType::Pull => {
+                anyhow::ensure!(
+                    ctx.path.is_dir(),
+                    "expected a directory of parts: {:?}",
+                    ctx.path
+                );
+
+                let temp_dir = temp_dir(cfg)?;
+                let output = Self::join(&ctx.path, temp_dir.path())?;
+                let _ = temp_dir.keep();
+
+                log_debug!("reassembled parts into {:?}", output);
+
+                Ok(HookContext {
+                    path: output,
+                    ..ctx
+                })
+            }
+        }
+    }
+
+    fn explain(&self, ctx: &HookContext, _cfg: &AppConfig) -> anyhow::Result<Vec<String>> {
+        if self.exec == HookExecType::Pull {
+            return Ok(vec![String::from(
+                "verifies the downloaded parts and joins them",
+            )]);
+        }
+
+        let part_size = self.part_size()?;
+
+        Ok(vec![match ctx.path.is_file() {
+            true => {
+                let size = std::fs::metadata(&ctx.path)
+                    .with_context(|| format!("failed to read metadata: {:?}", ctx.path))?
+                    .len();
+
+                format!(
+                    "splits {} bytes into {} part(s) of up to {} bytes",
+                    size,
+                    size.div_ceil(part_size),
+                    part_size
+                )
+            }
+            false => format!("splits the archive into parts of up to {} bytes", part_size),
+        }])
+    }
+}
@@ -0,0 +1,74 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const INDEX_VERSION: u32 = 1;
+
+/// File describing the parts, stored next to them.
+pub const INDEX_FILE: &str = "index.json";
+
+/// Extension of the directory holding the parts, e.g. `name.zip.parts`.
+pub const PARTS_EXTENSION: &str = "parts";
+
+/// File name of the part at `position`, starting at 1, e.g. `part-0001`.
+pub fn part_name(position: usize) -> String {
+    format!("part-{:04}", position)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkPart {
+    pub file: String,
+    pub size: u64,
+    pub digest: String,
+}
+
+/// Describes how to reassemble a split artifact from its parts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkIndex {
+    pub version: u32,
+
+    /// File name of the split artifact.
+    pub name: String,
+    pub size: u64,
+    pub digest: String,
+    pub part_size: u64,
+    pub parts: Vec<ChunkPart>,
+}
+
+impl ChunkIndex {
+    pub fn load(directory: &Path) -> anyhow::Result<Self> {
+        let path = directory.join(INDEX_FILE);
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read chunk index: {:?}", path))?;
+
+        let index: Self = serde_json::from_str(&content)
+            .with_context(|| format!("invalid chunk index: {:?}", path))?;
+
+        anyhow::ensure!(
+            index.version <= INDEX_VERSION,
+            "unsupported chunk index version: {}",
+            index.version
+        );
+
+        Ok(index)
+    }
+
+    pub fn save(&self, directory: &Path) -> anyhow::Result<()> {
+        let path = directory.join(INDEX_FILE);
+        let content = serde_json::to_string(self).context("failed to serialize chunk index")?;
+
+        // written aside and renamed, an interrupted split never leaves a truncated index
+        let mut temp_file = tempfile::NamedTempFile::new_in(directory)
+            .with_context(|| format!("failed to create temp file in {:?}", directory))?;
+
+        std::io::Write::write_all(&mut temp_file, content.as_bytes())
+            .with_context(|| format!("failed to write chunk index: {:?}", temp_file.path()))?;
+
+        temp_file
+            .persist(&path)
+            .with_context(|| format!("failed to replace chunk index: {:?}", path))?;
+
+        Ok(())
+    }
+}
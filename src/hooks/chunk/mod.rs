@@ -0,0 +1,6 @@
+pub mod config;
+pub mod hook;
+pub mod index;
+
+pub use super::chunk::hook::ChunkHookConfig;
+pub use super::chunk::index::PARTS_EXTENSION;
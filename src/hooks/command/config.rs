@@ -0,0 +1,51 @@
+use crate::{
+    config::prelude::{HookConfig, HookExecType, Hooks},
+    hooks::command::{CommandHookConfig, FailurePolicy},
+    log_info,
+};
+use anyhow::Context;
+use inquire::{Confirm, Text};
+
+impl CommandHookConfig {
+    pub fn build(exec_type: HookExecType) -> anyhow::Result<HookConfig> {
+        log_info!("configuring {} for {}", Hooks::Command, exec_type);
+
+        let command = Text::new("Command:")
+            .with_help_message(
+                "run with sh -c, env: RCLOUD_PATH, RCLOUD_LOCAL_PATH, RCLOUD_REMOTE_NAME, RCLOUD_DIRECTION, RCLOUD_HASH",
+            )
+            .with_validator(inquire::required!("command is required"))
+            .prompt()
+            .context("failed to get command")?;
+
+        let timeout_secs = Text::new("Timeout (seconds):")
+            .with_help_message("optional")
+            .prompt_skippable()
+            .context("failed to get timeout")?
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| s.trim().parse::<u64>())
+            .transpose()
+            .context("not a number")?;
+
+        let on_failure = FailurePolicy::select("On failure:")
+            .prompt()
+            .context("failed to select failure policy")?;
+
+        let after_sync = Confirm::new(match exec_type {
+            HookExecType::Push => "Run after the upload instead of before?",
+            HookExecType::Pull => "Run after local_path is updated instead of before?",
+        })
+        .with_default(false)
+        .prompt()
+        .context("failed to get confirmation")?;
+
+        Ok(HookConfig::Command(Self {
+            exec: exec_type,
+            command,
+            timeout_secs,
+            on_failure,
+            after_sync,
+            when: None,
+        }))
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    config::prelude::{AppConfig, Hook, HookExecType},
+    define_hook,
+    hooks::prelude::{HookContext, HookContextMetadata},
+    log_debug, log_info, log_warn,
+};
+use anyhow::Context;
+use inquire_derive::Selectable;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// What to do when the command exits with a non-zero status or times out.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Selectable, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FailurePolicy {
+    /// Fail the sync.
+    #[default]
+    Abort,
+    /// Log a warning and continue.
+    Warn,
+    /// Continue silently.
+    Ignore,
+}
+
+impl std::fmt::Display for FailurePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailurePolicy::Abort => write!(f, "abort"),
+            FailurePolicy::Warn => write!(f, "warn"),
+            FailurePolicy::Ignore => write!(f, "ignore"),
+        }
+    }
+}
+
+define_hook!(CommandHook {
+    command: String,
+    timeout_secs: Option<u64>,
+    on_failure: FailurePolicy,
+    after_sync: bool,
+});
+
+impl CommandHook {
+    /// Environment exposing the hook context to the command.
+    pub fn env(&self, ctx: &HookContext) -> Vec<(&'static str, String)> {
+        let direction = match self.exec {
+            HookExecType::Push => "push",
+            HookExecType::Pull => "pull",
+        };
+
+        vec![
+            ("RCLOUD_PATH", ctx.path.to_string_lossy().to_string()),
+            ("RCLOUD_PATH_ID", ctx.path_config.id.clone()),
+            ("RCLOUD_LOCAL_PATH", ctx.path_config.local_path.clone()),
+            ("RCLOUD_REMOTE_NAME", ctx.remote_config.remote_name.clone()),
+            ("RCLOUD_REMOTE_PATH", ctx.path_config.remote_path.clone()),
+            ("RCLOUD_DIRECTION", direction.to_string()),
+            (
+                "RCLOUD_HASH",
+                ctx.metadata
+                    .get(&HookContextMetadata::CalculatedHash)
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+        ]
+    }
+
+    fn shell(&self) -> std::process::Command {
+        #[cfg(windows)]
+        {
+            let mut command = std::process::Command::new("cmd");
+            command.args(["/C", &self.command]);
+            command
+        }
+
+        #[cfg(not(windows))]
+        {
+            let mut command = std::process::Command::new("sh");
+            command.args(["-c", &self.command]);
+            command
+        }
+    }
+
+    /// Runs the command and waits for it, killing it once the timeout is reached.
+    fn execute(&self, ctx: &HookContext) -> anyhow::Result<()> {
+        let mut child = self
+            .shell()
+            .envs(self.env(ctx))
+            .stdin(std::process::Stdio::null())
+            .spawn()
+            .with_context(|| format!("failed to spawn command: {}", self.command))?;
+
+        let deadline = self
+            .timeout_secs
+            .map(|secs| Instant::now() + Duration::from_secs(secs));
+
+        loop {
+            if let Some(status) = child.try_wait().context("failed to wait for command")? {
+                anyhow::ensure!(
+                    status.success(),
+                    "command exited with {}: {}",
+                    status,
+                    self.command
+                );
+
+                return Ok(());
+            }
+
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                child.kill().context("failed to kill command")?;
+                child.wait().context("failed to wait for command")?;
+
+                anyhow::bail!(
+                    "command timed out after {}s: {}",
+                    self.timeout_secs.unwrap_or_default(),
+                    self.command
+                );
+            }
+
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Runs the command, applying the failure policy.
+    pub fn run(&self, ctx: &HookContext) -> anyhow::Result<()> {
+        log_info!("running command: {}", self.command);
+
+        match (self.execute(ctx), self.on_failure) {
+            (Ok(()), _) => Ok(()),
+            (Err(e), FailurePolicy::Abort) => Err(e),
+            (Err(e), FailurePolicy::Warn) => {
+                log_warn!("{:#}", e);
+                Ok(())
+            }
+            (Err(e), FailurePolicy::Ignore) => {
+                log_debug!("ignoring command failure: {:#}", e);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Hook for CommandHook {
+    fn process(&self, ctx: HookContext, _cfg: &AppConfig) -> anyhow::Result<HookContext> {
+        if self.after_sync {
+            log_debug!(
+                "deferring command until the sync finished: {}",
+                self.command
+            );
+            return Ok(ctx);
+        }
+
+        self.run(&ctx)?;
+
+        Ok(ctx)
+    }
+
+    fn explain(&self, _ctx: &HookContext, _cfg: &AppConfig) -> anyhow::Result<Vec<String>> {
+        Ok(vec![format!(
+            "runs `{}` {}, on failure: {}{}",
+            self.command,
+            match self.after_sync {
+                true => "after the sync",
+                false => "before the transfer",
+            },
+            self.on_failure,
+            self.timeout_secs
+                .map(|secs| format!(", timeout: {}s", secs))
+                .unwrap_or_default()
+        )])
+    }
+}
@@ -0,0 +1,477 @@
+//! `when` expressions deciding whether a hook runs.
+//!
+//! ```text
+//! first_sync_today
+//! file_count > 100 and size < 2G
+//! not (hostname in ["work-laptop", "ci"])
+//! direction == "push" or weekday == "sun"
+//! ```
+//!
+//! Operators are `and`, `or`, `not`, `==`, `!=`, `<`, `<=`, `>`, `>=` and `in`
+//! (list membership or substring). Numbers accept size suffixes (`500K`, `10M`, `2G`).
+//!
+//! Variables:
+//! - `hostname`, `user`, `path_id`, `direction` (`"push"` or `"pull"`)
+//! - `file_count`, `size`: files and bytes of the content reaching the hook
+//! - `hour` (0-23), `weekday` (`"mon"` to `"sun"`), in local time
+//! - `syncs_today`, `first_sync_today`, `hours_since_last_sync`: successful syncs
+//!   of the path in the same direction, from the sync history
+//!
+//! Hooks renaming the upload (`Zip`, `Chunk`, renaming `External` plugins) cannot
+//! have a condition: skipping one would upload under another name, and the next
+//! pull would restore the stale object left under the expected one.
+
+use crate::{
+    config::prelude::{HookConfig, HookExecType},
+    hooks::{backup::retention::parse_size, prelude::HookContext},
+    log_warn,
+    utils::{
+        history::{History, HistoryEntry, SyncStatus},
+        host,
+    },
+};
+use anyhow::Context;
+use chrono::{DateTime, Datelike, Local, TimeZone, Timelike};
+use std::cell::OnceCell;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Number(f64),
+    Str(String),
+    List(Vec<Value>),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Str(s) => write!(f, "{:?}", s),
+            Value::List(items) => write!(
+                f,
+                "[{}]",
+                items
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Str(String),
+    Operator(Operator),
+    And,
+    Or,
+    Not,
+    True,
+    False,
+    OpenParen,
+    CloseParen,
+    OpenBracket,
+    CloseBracket,
+    Comma,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Literal(Value),
+    Variable(String),
+    List(Vec<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, Operator, Box<Expr>),
+}
+
+/// A parsed `when` expression.
+#[derive(Debug, Clone)]
+pub struct Condition {
+    expr: Expr,
+}
+
+fn tokenize(input: &str) -> anyhow::Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        let two = |next: char| chars.get(i + 1) == Some(&next);
+
+        let (token, length) = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => (Token::OpenParen, 1),
+            ')' => (Token::CloseParen, 1),
+            '[' => (Token::OpenBracket, 1),
+            ']' => (Token::CloseBracket, 1),
+            ',' => (Token::Comma, 1),
+            '=' if two('=') => (Token::Operator(Operator::Eq), 2),
+            '!' if two('=') => (Token::Operator(Operator::Ne), 2),
+            '<' if two('=') => (Token::Operator(Operator::Le), 2),
+            '>' if two('=') => (Token::Operator(Operator::Ge), 2),
+            '<' => (Token::Operator(Operator::Lt), 1),
+            '>' => (Token::Operator(Operator::Gt), 1),
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&q| q == c)
+                    .with_context(|| format!("unterminated string at {}", i))?;
+
+                let value: String = chars[i + 1..i + 1 + end].iter().collect();
+                (Token::Str(value), end + 2)
+            }
+            c if c.is_ascii_digit() => {
+                let length = chars[i..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_alphanumeric() || **c == '.')
+                    .count();
+
+                let literal: String = chars[i..i + length].iter().collect();
+
+                let number = match literal.parse::<f64>() {
+                    Ok(number) => number,
+                    Err(_) => parse_size(&literal)
+                        .with_context(|| format!("invalid number: {}", literal))?
+                        as f64,
+                };
+
+                (Token::Number(number), length)
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let length = chars[i..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+                    .count();
+
+                let word: String = chars[i..i + length].iter().collect();
+
+                let token = match word.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "in" => Token::Operator(Operator::In),
+                    "true" => Token::True,
+                    "false" => Token::False,
+                    _ => Token::Ident(word),
+                };
+
+                (token, length)
+            }
+            _ => anyhow::bail!("unexpected character '{}' at {}", c, i),
+        };
+
+        tokens.push(token);
+        i += length;
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> anyhow::Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => anyhow::bail!("expected {:?}, found {:?}", expected, token),
+            None => anyhow::bail!("expected {:?}, found end of expression", expected),
+        }
+    }
+
+    fn or(&mut self) -> anyhow::Result<Expr> {
+        let mut left = self.and()?;
+
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+
+        Ok(left)
+    }
+
+    fn and(&mut self) -> anyhow::Result<Expr> {
+        let mut left = self.not()?;
+
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            left = Expr::And(Box::new(left), Box::new(self.not()?));
+        }
+
+        Ok(left)
+    }
+
+    fn not(&mut self) -> anyhow::Result<Expr> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> anyhow::Result<Expr> {
+        let left = self.value()?;
+
+        match self.peek() {
+            Some(Token::Operator(operator)) => {
+                let operator = *operator;
+                self.next();
+                Ok(Expr::Compare(
+                    Box::new(left),
+                    operator,
+                    Box::new(self.value()?),
+                ))
+            }
+            _ => Ok(left),
+        }
+    }
+
+    fn value(&mut self) -> anyhow::Result<Expr> {
+        match self.next() {
+            Some(Token::Ident(name)) => Ok(Expr::Variable(name)),
+            Some(Token::Number(n)) => Ok(Expr::Literal(Value::Number(n))),
+            Some(Token::Str(s)) => Ok(Expr::Literal(Value::Str(s))),
+            Some(Token::True) => Ok(Expr::Literal(Value::Bool(true))),
+            Some(Token::False) => Ok(Expr::Literal(Value::Bool(false))),
+            Some(Token::OpenParen) => {
+                let expr = self.or()?;
+                self.expect(Token::CloseParen)?;
+                Ok(expr)
+            }
+            Some(Token::OpenBracket) => {
+                let mut items = Vec::new();
+
+                while self.peek() != Some(&Token::CloseBracket) {
+                    items.push(self.value()?);
+
+                    if self.peek() == Some(&Token::Comma) {
+                        self.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                self.expect(Token::CloseBracket)?;
+                Ok(Expr::List(items))
+            }
+            Some(token) => anyhow::bail!("unexpected {:?}", token),
+            None => anyhow::bail!("unexpected end of expression"),
+        }
+    }
+}
+
+/// Values a condition can read, computed on first use.
+pub struct ConditionContext<'a> {
+    hook: &'a HookContext,
+    direction: HookExecType,
+    now: DateTime<Local>,
+    history: History,
+    entries: OnceCell<Vec<HistoryEntry>>,
+    content: OnceCell<(u64, u64)>,
+}
+
+impl<'a> ConditionContext<'a> {
+    pub fn new(
+        hook: &'a HookContext,
+        direction: HookExecType,
+        history: History,
+        now: DateTime<Local>,
+    ) -> Self {
+        Self {
+            hook,
+            direction,
+            now,
+            history,
+            entries: OnceCell::new(),
+            content: OnceCell::new(),
+        }
+    }
+
+    fn history(&self) -> &[HistoryEntry] {
+        self.entries.get_or_init(|| match self.history.entries() {
+            Ok(entries) => entries,
+            Err(e) => {
+                log_warn!("failed to read sync history: {}", e);
+                Vec::new()
+            }
+        })
+    }
+
+    /// Successful syncs of the path in the hook direction, oldest first.
+    fn syncs(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.history().iter().filter(|entry| {
+            entry.path_id == self.hook.path_config.id
+                && entry.direction == self.direction
+                && entry.status == SyncStatus::Success
+        })
+    }
+
+    /// Number of files and total size of the content reaching the hook.
+    fn content(&self) -> (u64, u64) {
+        *self.content.get_or_init(|| {
+            walkdir::WalkDir::new(&self.hook.path)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_file())
+                .fold((0, 0), |(count, size), entry| {
+                    let length = entry.metadata().map(|m| m.len()).unwrap_or_default();
+                    (count + 1, size + length)
+                })
+        })
+    }
+
+    fn variable(&self, name: &str) -> anyhow::Result<Value> {
+        let midnight = self
+            .now
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .and_then(|dt| Local.from_local_datetime(&dt).earliest())
+            .map(|dt| dt.timestamp())
+            .unwrap_or_default();
+
+        Ok(match name {
+            "hostname" => Value::Str(host::hostname().unwrap_or_default()),
+            "user" => Value::Str(host::user().unwrap_or_default()),
+            "path_id" => Value::Str(self.hook.path_config.id.clone()),
+            "direction" => Value::Str(match self.direction {
+                HookExecType::Push => String::from("push"),
+                HookExecType::Pull => String::from("pull"),
+            }),
+            "file_count" => Value::Number(self.content().0 as f64),
+            "size" => Value::Number(self.content().1 as f64),
+            "hour" => Value::Number(self.now.hour() as f64),
+            "weekday" => Value::Str(self.now.weekday().to_string().to_lowercase()),
+            "syncs_today" => Value::Number(
+                self.syncs()
+                    .filter(|entry| entry.timestamp >= midnight)
+                    .count() as f64,
+            ),
+            "first_sync_today" => {
+                Value::Bool(!self.syncs().any(|entry| entry.timestamp >= midnight))
+            }
+            "hours_since_last_sync" => Value::Number(
+                self.syncs()
+                    .map(|entry| entry.timestamp)
+                    .max()
+                    .map(|last| (self.now.timestamp() - last) as f64 / 3600.0)
+                    .unwrap_or(f64::INFINITY),
+            ),
+            _ => anyhow::bail!("unknown variable: {}", name),
+        })
+    }
+}
+
+fn compare(left: &Value, operator: Operator, right: &Value) -> anyhow::Result<bool> {
+    Ok(match (operator, left, right) {
+        (Operator::Eq, _, _) => left == right,
+        (Operator::Ne, _, _) => left != right,
+        (Operator::In, item, Value::List(items)) => items.contains(item),
+        (Operator::In, Value::Str(needle), Value::Str(haystack)) => haystack.contains(needle),
+        (Operator::Lt, Value::Number(a), Value::Number(b)) => a < b,
+        (Operator::Le, Value::Number(a), Value::Number(b)) => a <= b,
+        (Operator::Gt, Value::Number(a), Value::Number(b)) => a > b,
+        (Operator::Ge, Value::Number(a), Value::Number(b)) => a >= b,
+        _ => anyhow::bail!("cannot compare {} with {}", left, right),
+    })
+}
+
+impl Expr {
+    fn evaluate(&self, ctx: &ConditionContext) -> anyhow::Result<Value> {
+        Ok(match self {
+            Expr::Literal(value) => value.clone(),
+            Expr::Variable(name) => ctx.variable(name)?,
+            Expr::List(items) => Value::List(
+                items
+                    .iter()
+                    .map(|item| item.evaluate(ctx))
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+            Expr::Not(expr) => Value::Bool(!expr.truthy(ctx)?),
+            Expr::And(left, right) => Value::Bool(left.truthy(ctx)? && right.truthy(ctx)?),
+            Expr::Or(left, right) => Value::Bool(left.truthy(ctx)? || right.truthy(ctx)?),
+            Expr::Compare(left, operator, right) => Value::Bool(compare(
+                &left.evaluate(ctx)?,
+                *operator,
+                &right.evaluate(ctx)?,
+            )?),
+        })
+    }
+
+    fn truthy(&self, ctx: &ConditionContext) -> anyhow::Result<bool> {
+        match self.evaluate(ctx)? {
+            Value::Bool(b) => Ok(b),
+            other => anyhow::bail!("expected a boolean, found {}", other),
+        }
+    }
+}
+
+impl std::str::FromStr for Condition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            position: 0,
+        };
+
+        let expr = parser.or()?;
+
+        if let Some(token) = parser.peek() {
+            anyhow::bail!("unexpected {:?} after the expression", token);
+        }
+
+        Ok(Self { expr })
+    }
+}
+
+impl Condition {
+    pub fn evaluate(&self, ctx: &ConditionContext) -> anyhow::Result<bool> {
+        self.expr.truthy(ctx)
+    }
+}
+
+/// Rejects a `when` condition on a hook renaming the upload.
+pub fn validate_when(hook: &HookConfig) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        hook.when().is_none() || !hook.modifies_filename(),
+        "{} hooks rename the upload and cannot have a when condition",
+        hook.hook_type()
+    );
+
+    Ok(())
+}
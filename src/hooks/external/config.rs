@@ -0,0 +1,80 @@
+use crate::{
+    config::prelude::{HookConfig, HookExecType, Hooks},
+    hooks::external::{
+        ExternalHookConfig,
+        hook::{find_plugin, is_valid_plugin_name, plugin_executable},
+    },
+    log_info, log_warn,
+};
+use anyhow::Context;
+use inquire::{Confirm, Text, validator::Validation};
+
+impl ExternalHookConfig {
+    pub fn build(exec_type: HookExecType) -> anyhow::Result<HookConfig> {
+        log_info!("configuring {} for {}", Hooks::External, exec_type);
+
+        let name = Text::new("Plugin name:")
+            .with_help_message("runs the rcloud-hook-<name> executable found on PATH")
+            .with_validator(|name: &str| {
+                Ok(match is_valid_plugin_name(name.trim()) {
+                    true => Validation::Valid,
+                    false => Validation::Invalid("use letters, digits, '-' and '_' only".into()),
+                })
+            })
+            .prompt()
+            .context("failed to get plugin name")?
+            .trim()
+            .to_string();
+
+        if find_plugin(&name).is_err() {
+            log_warn!(
+                "{} was not found on PATH, install it before syncing",
+                plugin_executable(&name)
+            );
+        }
+
+        let options = Text::new("Options (JSON):")
+            .with_help_message("sent to the plugin as is, optional")
+            .with_validator(|options: &str| {
+                Ok(
+                    match options.trim().is_empty()
+                        || serde_json::from_str::<serde_json::Value>(options).is_ok()
+                    {
+                        true => Validation::Valid,
+                        false => Validation::Invalid("invalid JSON".into()),
+                    },
+                )
+            })
+            .prompt_skippable()
+            .context("failed to get plugin options")?
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| serde_json::from_str(&s))
+            .transpose()
+            .context("failed to parse plugin options")?;
+
+        let modifies_filename = Confirm::new("Does the plugin change the file name?")
+            .with_help_message("e.g. it packs the content into an archive")
+            .with_default(false)
+            .prompt()
+            .context("failed to get confirmation")?;
+
+        let extension = match modifies_filename {
+            true => Text::new("Extension of the remote file:")
+                .with_help_message("e.g. tar.gz, optional")
+                .prompt_skippable()
+                .context("failed to get extension")?
+                .map(|s| s.trim().trim_start_matches('.').to_string())
+                .filter(|s| !s.is_empty()),
+            false => None,
+        };
+
+        Ok(HookConfig::External(Self {
+            exec: exec_type,
+            name,
+            options,
+            modifies_filename,
+            extension,
+            when: None,
+        }))
+    }
+}
@@ -0,0 +1,237 @@
+use crate::{
+    config::prelude::{AppConfig, Hook},
+    define_hook,
+    hooks::{
+        artifacts::{self, ArtifactDir},
+        external::protocol::{
+            PLUGIN_PREFIX, PROTOCOL_VERSION, PluginContext, PluginRequest, PluginResponse,
+        },
+        prelude::{HookContext, HookContextMetadata},
+    },
+    log_debug, log_info, log_warn,
+};
+use anyhow::Context;
+use std::{
+    ffi::OsStr,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+pub const WORK_DIR_PREFIX: &str = "rcloud-hook-";
+
+define_hook!(ExternalHook {
+    name: String,
+    options: Option<serde_json::Value>,
+    modifies_filename: bool,
+    extension: Option<String>,
+});
+
+/// Whether `name` can be used in a plugin executable name.
+pub fn is_valid_plugin_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// File name of the executable implementing the plugin `name`.
+pub fn plugin_executable(name: &str) -> String {
+    format!("{}{}{}", PLUGIN_PREFIX, name, std::env::consts::EXE_SUFFIX)
+}
+
+/// Looks up the plugin `name` in the directories of `search_path`, formatted like `PATH`.
+pub fn find_plugin_in(name: &str, search_path: &OsStr) -> Option<PathBuf> {
+    let executable = plugin_executable(name);
+
+    std::env::split_paths(search_path)
+        .map(|directory| directory.join(&executable))
+        .find(|candidate| candidate.is_file())
+}
+
+pub fn find_plugin(name: &str) -> anyhow::Result<PathBuf> {
+    anyhow::ensure!(is_valid_plugin_name(name), "invalid plugin name: {}", name);
+
+    let search_path = std::env::var_os("PATH").unwrap_or_default();
+
+    find_plugin_in(name, &search_path)
+        .with_context(|| format!("hook plugin not found on PATH: {}", plugin_executable(name)))
+}
+
+impl ExternalHook {
+    fn work_dir(cfg: &AppConfig) -> anyhow::Result<ArtifactDir> {
+        artifacts::create_dir(WORK_DIR_PREFIX, cfg)
+    }
+
+    fn request(&self, ctx: &HookContext, work_dir: &Path) -> anyhow::Result<Vec<u8>> {
+        let empty = serde_json::Value::Object(Default::default());
+
+        let request = PluginRequest {
+            version: PROTOCOL_VERSION,
+            exec: self.exec,
+            work_dir,
+            options: self.options.as_ref().unwrap_or(&empty),
+            context: PluginContext {
+                path: &ctx.path,
+                rclone_path: &ctx.rclone_path,
+                remote: &ctx.remote_config,
+                path_config: &ctx.path_config,
+                metadata: &ctx.metadata,
+            },
+        };
+
+        serde_json::to_vec(&request).context("failed to serialize plugin request")
+    }
+
+    /// Warns when the returned path does not match the configured naming.
+    fn check_name(&self, before: &Path, after: &Path) {
+        let name = after.file_name().map(|n| n.to_string_lossy());
+
+        match (self.modifies_filename, &self.extension) {
+            (false, _) if name != before.file_name().map(|n| n.to_string_lossy()) => log_warn!(
+                "plugin {} renamed {:?} to {:?} but is not set to change the file name",
+                self.name,
+                before,
+                after
+            ),
+            (true, Some(extension))
+                if !name.is_some_and(|n| n.ends_with(&format!(".{}", extension))) =>
+            {
+                log_debug!(
+                    "plugin {} output {:?} does not end with .{}, it is renamed on upload",
+                    self.name,
+                    after,
+                    extension
+                )
+            }
+            _ => {}
+        }
+    }
+
+    /// Applies a plugin response to the context it was sent.
+    fn apply(
+        &self,
+        mut ctx: HookContext,
+        response: PluginResponse,
+        work_dir: &Path,
+    ) -> anyhow::Result<HookContext> {
+        if let Some(path) = response.path {
+            let path = match path.is_absolute() {
+                true => path,
+                false => work_dir.join(path),
+            };
+
+            anyhow::ensure!(
+                path.exists(),
+                "plugin {} returned a path that does not exist: {:?}",
+                self.name,
+                path
+            );
+
+            self.check_name(&ctx.path, &path);
+            ctx.path = path;
+        }
+
+        for (key, value) in response.metadata {
+            match serde_json::from_value::<HookContextMetadata>(serde_json::Value::String(
+                key.clone(),
+            )) {
+                Ok(key) => {
+                    ctx.metadata.insert(key, value);
+                }
+                Err(_) => log_debug!("ignoring unknown plugin metadata: {}", key),
+            }
+        }
+
+        Ok(ctx)
+    }
+
+    /// Runs the `plugin` executable with the context on stdin and applies its response.
+    pub fn invoke(
+        &self,
+        plugin: &Path,
+        ctx: HookContext,
+        cfg: &AppConfig,
+    ) -> anyhow::Result<HookContext> {
+        let work_dir = Self::work_dir(cfg)?;
+        let request = self.request(&ctx, work_dir.path())?;
+
+        log_debug!("invoking hook plugin: {:?}", plugin);
+
+        let mut child = std::process::Command::new(plugin)
+            .env("RCLOUD_HOOK_PROTOCOL", PROTOCOL_VERSION.to_string())
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .with_context(|| format!("failed to spawn hook plugin: {:?}", plugin))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            match stdin.write_all(&request) {
+                Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                    return Err(e).context("failed to write plugin request");
+                }
+                _ => {}
+            }
+        }
+
+        let output = child
+            .wait_with_output()
+            .context("failed to wait for hook plugin")?;
+
+        anyhow::ensure!(
+            output.status.success(),
+            "hook plugin {} exited with {}",
+            self.name,
+            output.status
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        let response = match stdout.trim().is_empty() {
+            true => PluginResponse::default(),
+            false => serde_json::from_str(stdout.trim())
+                .with_context(|| format!("invalid response from hook plugin {}", self.name))?,
+        };
+
+        let ctx = self.apply(ctx, response, work_dir.path())?;
+
+        // the work dir is removed unless the plugin left its output there
+        if ctx.path.starts_with(work_dir.path()) {
+            let _ = work_dir.keep();
+        }
+
+        Ok(ctx)
+    }
+}
+
+impl Hook for ExternalHook {
+    fn process(&self, ctx: HookContext, cfg: &AppConfig) -> anyhow::Result<HookContext> {
+        let plugin = find_plugin(&self.name)?;
+
+        log_info!("running hook plugin: {}", self.name);
+
+        self.invoke(&plugin, ctx, cfg)
+    }
+
+    fn explain(&self, _ctx: &HookContext, _cfg: &AppConfig) -> anyhow::Result<Vec<String>> {
+        let mut lines = vec![match find_plugin(&self.name) {
+            Ok(plugin) => format!("runs plugin {}", plugin.display()),
+            Err(e) => format!("{:#}", e),
+        }];
+
+        if let Some(options) = &self.options {
+            lines.push(format!("options: {}", options));
+        }
+
+        if self.modifies_filename {
+            lines.push(format!(
+                "renames the content{}",
+                self.extension
+                    .as_ref()
+                    .map(|extension| format!(" to *.{}", extension))
+                    .unwrap_or_default()
+            ));
+        }
+
+        Ok(lines)
+    }
+}
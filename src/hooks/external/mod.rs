@@ -0,0 +1,5 @@
+pub mod config;
+pub mod hook;
+pub mod protocol;
+
+pub use super::external::hook::ExternalHookConfig;
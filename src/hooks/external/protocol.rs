@@ -0,0 +1,69 @@
+//! JSON protocol spoken with external hook plugins.
+//!
+//! A plugin is an executable named `rcloud-hook-<name>` found on `PATH`. For every
+//! run it receives a [`PluginRequest`] as JSON on stdin and must print a
+//! [`PluginResponse`] as JSON on stdout, then exit with status 0. Anything written
+//! to stderr is shown to the user as is.
+//!
+//! ```json
+//! {"version": 1, "exec": "Push", "work_dir": "/tmp/rcloud-hook-x",
+//!  "options": {"level": 3},
+//!  "context": {"path": "/home/me/notes", "rclone_path": "rclone",
+//!              "remote": {...}, "path_config": {...},
+//!              "metadata": {"calculated_hash": "..."}}}
+//! ```
+//!
+//! The plugin writes its output in `work_dir` and answers with the new path,
+//! relative paths being resolved against `work_dir`:
+//!
+//! ```json
+//! {"path": "notes.tar.gz", "metadata": {"zip_checksum": "..."}}
+//! ```
+//!
+//! Both fields are optional, an empty object keeps the context unchanged.
+
+use crate::{
+    config::prelude::{HookExecType, PathConfig, Remote},
+    hooks::prelude::HookContextMetadata,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Prefix of the plugin executables, followed by the plugin name.
+pub const PLUGIN_PREFIX: &str = "rcloud-hook-";
+
+#[derive(Debug, Serialize)]
+pub struct PluginContext<'a> {
+    pub path: &'a Path,
+    pub rclone_path: &'a str,
+    pub remote: &'a Remote,
+    pub path_config: &'a PathConfig,
+    pub metadata: &'a HashMap<HookContextMetadata, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PluginRequest<'a> {
+    pub version: u32,
+    pub exec: HookExecType,
+
+    /// Directory owned by the plugin for this run. It is removed once the plugin
+    /// exits, unless the returned `path` is inside it, then it lives until the sync is over.
+    pub work_dir: &'a Path,
+    pub options: &'a serde_json::Value,
+    pub context: PluginContext<'a>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PluginResponse {
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+
+    /// Merged into the context metadata, unknown keys are ignored.
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
@@ -1,9 +1,15 @@
 use crate::{
     config::prelude::*,
-    hooks::{backup::hook::BackupHookConfig, zip::hook::ZipHookConfig},
+    hooks::{
+        backup::hook::BackupHookConfig, chunk::hook::ChunkHookConfig,
+        command::hook::CommandHookConfig, condition::Condition, external::hook::ExternalHookConfig,
+        sqlite::hook::SqliteHookConfig, template::hook::TemplateHookConfig,
+        zip::hook::ZipHookConfig,
+    },
 };
-use anyhow::{Context, Ok};
+use anyhow::Context;
 use bon::Builder;
+use inquire::{Text, validator::Validation};
 
 #[derive(Builder)]
 pub struct HookBuilder {
@@ -24,13 +30,54 @@ impl TryFrom<HookBuilder> for HookConfig {
             .hook_exec_type
             .expect("hook exec type must be declared");
 
-        let config = match hook_type {
+        let mut config = match hook_type {
             Hooks::Zip => ZipHookConfig::build(exec_type).context("failed to build zip hook")?,
             Hooks::Backup => {
                 BackupHookConfig::build(exec_type).context("failed to build backup hook")?
             }
+            Hooks::Command => {
+                CommandHookConfig::build(exec_type).context("failed to build command hook")?
+            }
+            Hooks::External => {
+                ExternalHookConfig::build(exec_type).context("failed to build external hook")?
+            }
+            Hooks::Sqlite => {
+                SqliteHookConfig::build(exec_type).context("failed to build sqlite hook")?
+            }
+            Hooks::Template => {
+                TemplateHookConfig::build(exec_type).context("failed to build template hook")?
+            }
+            Hooks::Chunk => {
+                ChunkHookConfig::build(exec_type).context("failed to build chunk hook")?
+            }
         };
 
+        if !config.modifies_filename() {
+            config.set_when(prompt_when()?);
+        }
+
         Ok(config)
     }
 }
+
+fn prompt_when() -> anyhow::Result<Option<String>> {
+    let when = Text::new("Run when:")
+        .with_help_message(
+            "optional condition, e.g. first_sync_today, file_count > 100, hostname != \"laptop\"",
+        )
+        .with_validator(|when: &str| {
+            Ok(match when.trim() {
+                "" => Validation::Valid,
+                when => match when.parse::<Condition>() {
+                    Ok(_) => Validation::Valid,
+                    Err(e) => Validation::Invalid(format!("invalid condition: {}", e).into()),
+                },
+            })
+        })
+        .prompt_skippable()
+        .context("failed to get condition")?;
+
+    Ok(when
+        .map(|when| when.trim().to_string())
+        .filter(|when| !when.is_empty()))
+}
@@ -1,7 +1,9 @@
 use crate::config::prelude::{PathConfig, Remote};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum HookContextMetadata {
     SourceLocalPath,
     SourceRemotePath,
@@ -35,7 +35,7 @@ macro_rules! register_hooks {
             pub fn modifies_filename(&self) -> bool {
                 match self {
                     $(
-                        HookConfig::$variant(_) => $modifies,
+                        HookConfig::$variant(cfg) => $modifies(cfg),
                     )*
                 }
             }
@@ -48,6 +48,22 @@ macro_rules! register_hooks {
                 }
             }
 
+            pub fn when(&self) -> Option<&str> {
+                match self {
+                    $(
+                        HookConfig::$variant(cfg) => cfg.when.as_deref(),
+                    )*
+                }
+            }
+
+            pub fn set_when(&mut self, when: Option<String>) {
+                match self {
+                    $(
+                        HookConfig::$variant(cfg) => cfg.when = when,
+                    )*
+                }
+            }
+
             pub fn hook_type(&self) -> &Hooks {
                 match self {
                     $(
@@ -61,9 +77,14 @@ macro_rules! register_hooks {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 match self {
                     $(
-                        HookConfig::$variant(cfg) => $display_fn(cfg, f),
+                        HookConfig::$variant(cfg) => $display_fn(cfg, f)?,
                     )*
                 }
+
+                match self.when() {
+                    Some(when) => write!(f, " when {}", when),
+                    None => Ok(()),
+                }
             }
         }
 
@@ -93,7 +114,11 @@ macro_rules! define_hook {
             #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
             pub struct [<$hook_name Config>] {
                 pub exec: $crate::config::hook_config::HookExecType,
-                $(pub $field: $field_ty),*
+                $(pub $field: $field_ty,)*
+
+                /// Condition deciding whether the hook runs, see `hooks::condition`.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub when: Option<String>,
             }
         }
 
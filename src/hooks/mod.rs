@@ -1,6 +1,13 @@
+pub mod artifacts;
 pub mod backup;
+pub mod chunk;
+pub mod command;
+pub mod condition;
+pub mod external;
 pub mod hook_builder;
 pub mod hook_context;
 pub mod macros;
 pub mod prelude;
+pub mod sqlite;
+pub mod template;
 pub mod zip;
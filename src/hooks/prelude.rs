@@ -1,4 +1,9 @@
 pub use super::backup::hook::{BackupHook, BackupHookConfig};
+pub use super::chunk::hook::{ChunkHook, ChunkHookConfig};
+pub use super::command::hook::{CommandHook, CommandHookConfig};
+pub use super::external::hook::{ExternalHook, ExternalHookConfig};
 pub use super::hook_builder::HookBuilder;
 pub use super::hook_context::{HookContext, HookContextMetadata};
+pub use super::sqlite::hook::{SqliteHook, SqliteHookConfig};
+pub use super::template::hook::{TemplateHook, TemplateHookConfig};
 pub use super::zip::hook::{ZipHook, ZipHookConfig};
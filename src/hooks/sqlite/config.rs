@@ -0,0 +1,29 @@
+use crate::{
+    config::prelude::{HookConfig, HookExecType, Hooks},
+    hooks::sqlite::{SqliteHookConfig, hook::DEFAULT_EXTENSIONS},
+    log_info,
+};
+use anyhow::Context;
+use inquire::Text;
+
+impl SqliteHookConfig {
+    pub fn build(exec_type: HookExecType) -> anyhow::Result<HookConfig> {
+        log_info!("configuring {} for {}", Hooks::Sqlite, exec_type);
+
+        let extensions = Text::new("Database extensions:")
+            .with_default(&DEFAULT_EXTENSIONS.join(","))
+            .with_help_message("comma-separated, only files with a SQLite header are handled")
+            .prompt()
+            .context("failed to get database extensions")?
+            .split(',')
+            .map(|e| e.trim().trim_start_matches('.').to_string())
+            .filter(|e| !e.is_empty())
+            .collect::<Vec<_>>();
+
+        Ok(HookConfig::Sqlite(Self {
+            exec: exec_type,
+            extensions: (!extensions.is_empty()).then_some(extensions),
+            when: None,
+        }))
+    }
+}
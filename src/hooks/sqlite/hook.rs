@@ -0,0 +1,291 @@
+use crate::{
+    config::prelude::{AppConfig, Hook, HookExecType},
+    define_hook,
+    hooks::{
+        artifacts::{self, ArtifactDir},
+        prelude::HookContext,
+    },
+    log_debug, log_info,
+    utils::filter::PathFilter,
+};
+use anyhow::Context;
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub const SNAPSHOT_PREFIX: &str = "rcloud-sqlite-";
+
+/// Every SQLite database starts with this header.
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Files SQLite keeps next to a database while it is open.
+const SIDECAR_SUFFIXES: [&str; 3] = ["-wal", "-shm", "-journal"];
+
+/// How long to wait for a writer to release its lock before failing.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub const DEFAULT_EXTENSIONS: [&str; 2] = ["db", "sqlite"];
+
+define_hook!(SqliteHook {
+    extensions: Option<Vec<String>>,
+});
+
+/// Whether `path` holds a SQLite database, whatever its extension.
+fn has_sqlite_header(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok_and(|_| &header == SQLITE_HEADER)
+}
+
+impl SqliteHook {
+    pub fn extensions(&self) -> Vec<String> {
+        match &self.extensions {
+            Some(extensions) if !extensions.is_empty() => extensions.clone(),
+            _ => DEFAULT_EXTENSIONS.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    /// Exclude patterns matching the journal files of the handled databases.
+    ///
+    /// Snapshots never carry them, so they are left out of the path's content
+    /// hash too, otherwise the live journal would make every push differ.
+    pub fn sidecar_patterns(&self) -> Vec<String> {
+        self.extensions()
+            .iter()
+            .flat_map(|e| {
+                SIDECAR_SUFFIXES
+                    .iter()
+                    .map(move |s| format!("*.{}{}", e, s))
+            })
+            .collect()
+    }
+
+    /// Whether `path` is a database handled by the hook.
+    pub fn is_database(&self, path: &Path) -> bool {
+        let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+            return false;
+        };
+
+        self.extensions()
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(extension))
+            && has_sqlite_header(path)
+    }
+
+    /// Whether `path` is a `-wal`, `-shm` or `-journal` file of a handled database.
+    fn is_sidecar(&self, path: &Path) -> bool {
+        let name = path.to_string_lossy();
+
+        SIDECAR_SUFFIXES.iter().any(|suffix| {
+            name.strip_suffix(suffix)
+                .is_some_and(|database| self.is_database(Path::new(database)))
+        })
+    }
+
+    /// Copies a live database to `target` with the online backup API.
+    ///
+    /// The snapshot is consistent even while another process writes to the
+    /// database, and includes the content still in its write-ahead log.
+    pub fn snapshot_database(source: &Path, target: &Path) -> anyhow::Result<()> {
+        let connection = rusqlite::Connection::open_with_flags(
+            source,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .with_context(|| format!("failed to open database: {:?}", source))?;
+
+        connection
+            .busy_timeout(BUSY_TIMEOUT)
+            .context("failed to set busy timeout")?;
+
+        connection
+            .backup(rusqlite::MAIN_DB, target, None)
+            .with_context(|| format!("failed to back up database {:?}", source))
+    }
+
+    /// Runs `PRAGMA integrity_check`, failing with the reported problems.
+    pub fn check_integrity(path: &Path) -> anyhow::Result<()> {
+        let connection =
+            rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+                .with_context(|| format!("failed to open database: {:?}", path))?;
+
+        let mut statement = connection
+            .prepare("PRAGMA integrity_check")
+            .context("failed to prepare integrity check")?;
+
+        let problems = statement
+            .query_map([], |row| row.get::<_, String>(0))
+            .context("failed to run integrity check")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("failed to read integrity check")?;
+
+        anyhow::ensure!(
+            problems == ["ok"],
+            "database {:?} is corrupted: {}",
+            path,
+            problems.join("; ")
+        );
+
+        Ok(())
+    }
+
+    fn snapshot_dir(cfg: &AppConfig) -> anyhow::Result<ArtifactDir> {
+        artifacts::create_dir(SNAPSHOT_PREFIX, cfg)
+    }
+
+    /// Copies `source` to `target`, snapshotting databases and leaving out their sidecars.
+    ///
+    /// # Returns
+    /// The number of databases snapshotted.
+    fn snapshot_file(&self, source: &Path, target: &Path) -> anyhow::Result<usize> {
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create directory: {:?}", parent))?;
+        }
+
+        if self.is_database(source) {
+            log_info!("snapshotting database: {:?}", source);
+            Self::snapshot_database(source, target)?;
+            return Ok(1);
+        }
+
+        if self.is_sidecar(source) {
+            log_debug!("skipping database sidecar: {:?}", source);
+            return Ok(0);
+        }
+
+        std::fs::copy(source, target)
+            .with_context(|| format!("failed to copy {:?} to {:?}", source, target))?;
+
+        Ok(0)
+    }
+
+    /// Writes a consistent copy of `source` as `destination/<name>`.
+    pub fn snapshot(
+        &self,
+        source: &Path,
+        destination: &Path,
+        filter: &PathFilter,
+    ) -> anyhow::Result<PathBuf> {
+        let name = source
+            .file_name()
+            .with_context(|| format!("failed to get file name of {:?}", source))?;
+
+        let root = destination.join(name);
+
+        let databases = match source.is_dir() {
+            true => {
+                std::fs::create_dir_all(&root)
+                    .with_context(|| format!("failed to create directory: {:?}", root))?;
+
+                let mut databases = 0;
+
+                for entry in filter.walk(source) {
+                    let relative = entry
+                        .path()
+                        .strip_prefix(source)
+                        .context("failed to build relative path")?;
+
+                    databases += self.snapshot_file(entry.path(), &root.join(relative))?;
+                }
+
+                databases
+            }
+            false => self.snapshot_file(source, &root)?,
+        };
+
+        log_info!("snapshotted {} database(s)", databases);
+
+        Ok(root)
+    }
+
+    /// Checks the integrity of every database under `path`.
+    pub fn verify(&self, path: &Path) -> anyhow::Result<usize> {
+        let mut databases = 0;
+
+        for entry in walkdir::WalkDir::new(path) {
+            let entry = entry.with_context(|| format!("failed to walk {:?}", path))?;
+
+            if entry.file_type().is_file() && self.is_database(entry.path()) {
+                log_debug!("checking database integrity: {:?}", entry.path());
+                Self::check_integrity(entry.path())?;
+                databases += 1;
+            }
+        }
+
+        Ok(databases)
+    }
+}
+
+impl Hook for SqliteHook {
+    fn process(&self, ctx: HookContext, cfg: &AppConfig) -> anyhow::Result<HookContext> {
+        anyhow::ensure!(
+            ctx.file_exists(),
+            "source file does not exists: {:?}",
+            &ctx.path
+        );
+
+        match self.exec {
+            HookExecType::Push => {
+                let filter = PathFilter::for_root(&ctx.path_config, &ctx.path)
+                    .context("failed to build path filter")?;
+
+                let snapshot_dir = Self::snapshot_dir(cfg)?;
+                let snapshot = self.snapshot(&ctx.path, snapshot_dir.path(), &filter)?;
+                let _ = snapshot_dir.keep();
+
+                Ok(HookContext {
+                    path: snapshot,
+                    ..ctx
+                })
+            }
+            HookExecType::Pull => {
+                let databases = self
+                    .verify(&ctx.path)
+                    .context("refusing to replace the local databases")?;
+
+                log_info!("{} database(s) passed the integrity check", databases);
+
+                Ok(ctx)
+            }
+        }
+    }
+
+    fn explain(&self, ctx: &HookContext, _cfg: &AppConfig) -> anyhow::Result<Vec<String>> {
+        let action = match self.exec {
+            HookExecType::Push => "snapshots",
+            HookExecType::Pull => "checks the integrity of",
+        };
+
+        if !ctx.path.exists() {
+            return Ok(vec![format!(
+                "{} the *.{} databases of the content",
+                action,
+                self.extensions().join(", *.")
+            )]);
+        }
+
+        let filter = PathFilter::for_root(&ctx.path_config, &ctx.path)
+            .context("failed to build path filter")?;
+
+        let databases: Vec<_> = match ctx.path.is_dir() {
+            true => filter
+                .walk(&ctx.path)
+                .map(|entry| entry.into_path())
+                .filter(|path| self.is_database(path))
+                .collect(),
+            false => Some(ctx.path.clone())
+                .filter(|path| self.is_database(path))
+                .into_iter()
+                .collect(),
+        };
+
+        let mut lines = vec![format!("{} {} database(s)", action, databases.len())];
+        lines.extend(databases.iter().map(|path| format!("+ {}", path.display())));
+
+        Ok(lines)
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{
+    config::prelude::{HookConfig, HookExecType, Hooks},
+    hooks::template::TemplateHookConfig,
+    log_info,
+};
+use anyhow::Context;
+use inquire::Text;
+
+impl TemplateHookConfig {
+    pub fn build(exec_type: HookExecType) -> anyhow::Result<HookConfig> {
+        log_info!("configuring {} for {}", Hooks::Template, exec_type);
+
+        let patterns = Text::new("Templated files:")
+            .with_help_message(
+                "comma-separated globs, e.g. .gitconfig,*.toml. variables come from [template.variables]",
+            )
+            .with_validator(inquire::required!("at least one pattern is required"))
+            .prompt()
+            .context("failed to get templated files")?
+            .split(',')
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>();
+
+        for pattern in &patterns {
+            globset::Glob::new(pattern)
+                .with_context(|| format!("invalid glob pattern: {}", pattern))?;
+        }
+
+        Ok(HookConfig::Template(Self {
+            exec: exec_type,
+            patterns,
+            when: None,
+        }))
+    }
+}
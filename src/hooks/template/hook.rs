@@ -0,0 +1,281 @@
+use crate::{
+    config::prelude::{AppConfig, Hook, HookExecType},
+    define_hook,
+    hooks::{
+        artifacts::{self, ArtifactDir},
+        prelude::HookContext,
+    },
+    log_debug, log_info,
+    utils::filter::PathFilter,
+};
+use anyhow::Context;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+pub const TEMPLATE_PREFIX: &str = "rcloud-template-";
+
+define_hook!(TemplateHook {
+    patterns: Vec<String>,
+});
+
+/// Word boundary to anchor a value edge, only needed next to a word character.
+fn word_boundary(c: Option<char>) -> &'static str {
+    match c.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+        true => r"\b",
+        false => "",
+    }
+}
+
+/// Replaces the machine values in `content` with `${RCLOUD_<NAME>}` placeholders.
+///
+/// Longer values are replaced first, so `/home/me/work` wins over `/home/me`.
+/// Values starting or ending with a word character only match whole words,
+/// which keeps a user named `me` from rewriting `some`.
+pub fn strip(content: &str, variables: &BTreeMap<String, String>) -> anyhow::Result<String> {
+    let mut values: Vec<(&String, &String)> = variables.iter().collect();
+    values.sort_by_key(|(_, value)| std::cmp::Reverse(value.len()));
+
+    if values.is_empty() {
+        return Ok(content.to_string());
+    }
+
+    let alternatives = values
+        .iter()
+        .map(|(_, value)| {
+            format!(
+                "{}{}{}",
+                word_boundary(value.chars().next()),
+                regex::escape(value),
+                word_boundary(value.chars().last()),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let re = regex::Regex::new(&format!("(?:{})", alternatives.join("|")))
+        .context("failed to build template regex")?;
+
+    Ok(re
+        .replace_all(content, |captures: &regex::Captures| {
+            let matched = &captures[0];
+
+            values
+                .iter()
+                .find(|(_, value)| value.as_str() == matched)
+                .map(|(name, _)| format!("${{{}}}", name))
+                .unwrap_or_else(|| matched.to_string())
+        })
+        .to_string())
+}
+
+/// Renders the `${RCLOUD_<NAME>}` placeholders in `content`, leaving unknown variables as is.
+pub fn render(content: &str, variables: &BTreeMap<String, String>) -> String {
+    shellexpand::env_with_context_no_errors(content, |name| variables.get(name)).to_string()
+}
+
+impl TemplateHook {
+    fn matcher(&self) -> anyhow::Result<globset::GlobSet> {
+        let mut builder = globset::GlobSetBuilder::new();
+
+        for pattern in &self.patterns {
+            let glob = match pattern.strip_prefix('/') {
+                Some(anchored) => anchored.to_string(),
+                None => format!("**/{}", pattern),
+            };
+
+            builder.add(
+                globset::Glob::new(&glob)
+                    .with_context(|| format!("invalid glob pattern: {}", pattern))?,
+            );
+        }
+
+        builder.build().context("failed to build glob set")
+    }
+
+    fn template_dir(cfg: &AppConfig) -> anyhow::Result<ArtifactDir> {
+        artifacts::create_dir(TEMPLATE_PREFIX, cfg)
+    }
+
+    /// Rewrites `file` with `apply` if it is text, leaving binary files untouched.
+    ///
+    /// # Returns
+    /// Whether the file content changed.
+    fn rewrite(
+        file: &Path,
+        target: &Path,
+        apply: &impl Fn(&str) -> anyhow::Result<String>,
+    ) -> anyhow::Result<bool> {
+        let bytes =
+            std::fs::read(file).with_context(|| format!("failed to read file: {:?}", file))?;
+
+        let Ok(content) = String::from_utf8(bytes) else {
+            log_debug!("skipping non UTF-8 file: {:?}", file);
+
+            if file != target {
+                std::fs::copy(file, target)
+                    .with_context(|| format!("failed to copy {:?} to {:?}", file, target))?;
+            }
+
+            return Ok(false);
+        };
+
+        let processed = apply(&content)?;
+
+        std::fs::write(target, &processed)
+            .with_context(|| format!("failed to write file: {:?}", target))?;
+
+        Ok(processed != content)
+    }
+
+    /// Files of `source` with their path relative to it, `source` itself if it is a file.
+    fn files(source: &Path, filter: &PathFilter) -> anyhow::Result<Vec<(PathBuf, PathBuf)>> {
+        match source.is_dir() {
+            true => filter
+                .walk(source)
+                .map(|entry| {
+                    let relative = entry
+                        .path()
+                        .strip_prefix(source)
+                        .context("failed to build relative path")?
+                        .to_path_buf();
+
+                    Ok((entry.path().to_path_buf(), relative))
+                })
+                .collect(),
+            false => Ok(vec![(
+                source.to_path_buf(),
+                PathBuf::from(source.file_name().context("failed to get file name")?),
+            )]),
+        }
+    }
+
+    /// Applies `apply` to the files of `source` matching the patterns, writing to `target`.
+    ///
+    /// Non matching files are copied as is unless `source` and `target` are the same.
+    fn process_tree(
+        &self,
+        source: &Path,
+        target: &Path,
+        filter: &PathFilter,
+        apply: impl Fn(&str) -> anyhow::Result<String>,
+    ) -> anyhow::Result<usize> {
+        let matcher = self.matcher()?;
+        let mut changed = 0;
+
+        for (file, relative) in Self::files(source, filter)? {
+            let destination = match source.is_dir() {
+                true => target.join(&relative),
+                false => target.to_path_buf(),
+            };
+
+            if let Some(parent) = destination.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to create directory: {:?}", parent))?;
+            }
+
+            if matcher.is_match(&relative) {
+                if Self::rewrite(&file, &destination, &apply)? {
+                    log_info!("templated: {}", relative.display());
+                    changed += 1;
+                }
+            } else if file != destination {
+                std::fs::copy(&file, &destination)
+                    .with_context(|| format!("failed to copy {:?} to {:?}", file, destination))?;
+            }
+        }
+
+        Ok(changed)
+    }
+}
+
+impl Hook for TemplateHook {
+    fn process(&self, ctx: HookContext, cfg: &AppConfig) -> anyhow::Result<HookContext> {
+        anyhow::ensure!(
+            ctx.file_exists(),
+            "source file does not exists: {:?}",
+            &ctx.path
+        );
+
+        let variables = cfg.template_variables()?;
+
+        log_debug!(
+            "template variables: {:?}",
+            variables.keys().collect::<Vec<_>>()
+        );
+
+        let filter = PathFilter::for_root(&ctx.path_config, &ctx.path)
+            .context("failed to build path filter")?;
+
+        match self.exec {
+            HookExecType::Push => {
+                let template_dir = Self::template_dir(cfg)?;
+                let target = template_dir.path().join(
+                    ctx.path
+                        .file_name()
+                        .with_context(|| format!("failed to get file name of {:?}", ctx.path))?,
+                );
+
+                let changed = self.process_tree(&ctx.path, &target, &filter, |content| {
+                    strip(content, &variables)
+                })?;
+                let _ = template_dir.keep();
+
+                log_info!("replaced machine values in {} file(s)", changed);
+
+                Ok(HookContext {
+                    path: target,
+                    ..ctx
+                })
+            }
+            HookExecType::Pull => {
+                // downloaded content lives in a temp dir, it is rendered in place
+                let changed = self.process_tree(&ctx.path, &ctx.path, &filter, |content| {
+                    Ok(render(content, &variables))
+                })?;
+
+                log_info!("rendered {} file(s)", changed);
+
+                Ok(ctx)
+            }
+        }
+    }
+
+    fn explain(&self, ctx: &HookContext, cfg: &AppConfig) -> anyhow::Result<Vec<String>> {
+        let variables = cfg.template_variables()?;
+
+        let mut lines = vec![format!(
+            "{} {:?} in the files matching {:?}",
+            match self.exec {
+                HookExecType::Push => "replaces the values of",
+                HookExecType::Pull => "renders",
+            },
+            variables.keys().collect::<Vec<_>>(),
+            self.patterns
+        )];
+
+        if self.exec == HookExecType::Pull || !ctx.path.exists() {
+            return Ok(lines);
+        }
+
+        let matcher = self.matcher()?;
+        let filter = PathFilter::for_root(&ctx.path_config, &ctx.path)
+            .context("failed to build path filter")?;
+
+        for (file, relative) in Self::files(&ctx.path, &filter)? {
+            if !matcher.is_match(&relative) {
+                continue;
+            }
+
+            let Ok(content) = std::fs::read_to_string(&file) else {
+                continue;
+            };
+
+            if strip(&content, &variables)? != content {
+                lines.push(format!("~ {}", relative.display()));
+            }
+        }
+
+        Ok(lines)
+    }
+}
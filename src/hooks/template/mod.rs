@@ -0,0 +1,4 @@
+pub mod config;
+pub mod hook;
+
+pub use super::template::hook::TemplateHookConfig;
@@ -35,12 +35,14 @@ impl ZipHookConfig {
                     exec: HookExecType::Push,
                     level: Some(level),
                     exclude,
+                    when: None,
                 }))
             }
             HookExecType::Pull => Ok(HookConfig::Zip(Self {
                 exec: HookExecType::Pull,
                 level: None,
                 exclude: None,
+                when: None,
             })),
         }
     }
@@ -1,15 +1,19 @@
 use crate::{
     config::prelude::{AppConfig, Hook, HookExecType},
     define_hook,
-    hooks::prelude::{HookContext, HookContextMetadata},
-    log_debug, log_info, utils,
+    hooks::{
+        artifacts,
+        prelude::{HookContext, HookContextMetadata},
+    },
+    log_debug, log_info,
+    utils::{self, filter::PathFilter},
 };
 use anyhow::Context;
 use std::io::Write;
 
-const ZIP_PREFIX: &str = "rcloud-zip-";
+pub const ZIP_PREFIX: &str = "rcloud-zip-";
 const ZIP_SUFFIX: &str = ".zip";
-const EXTRACT_PREFIX: &str = "rcloud-extract-";
+pub const EXTRACT_PREFIX: &str = "rcloud-extract-";
 
 define_hook!(ZipHook {
     level: Option<i64>,
@@ -46,7 +50,14 @@ impl Hook for ZipHook {
 
                 match path.is_dir() {
                     true => self
-                        .process_directory(path, &mut zip, options, exclude_set.as_ref())
+                        .process_directory(
+                            path,
+                            &mut zip,
+                            options,
+                            exclude_set.as_ref(),
+                            &PathFilter::for_root(&ctx.path_config, path)
+                                .context("failed to build path filter")?,
+                        )
                         .context("failed to process directory")?,
                     false => self
                         .process_file(path, &mut zip, options)
@@ -58,21 +69,22 @@ impl Hook for ZipHook {
 
                 let checksum = utils::hash::Hash::hash_bytes(zip_bytes);
 
-                let mut temp_file = match Self::base_temp_dir(cfg)? {
-                    Some(directory) => tempfile::Builder::new()
-                        .prefix(ZIP_PREFIX)
-                        .suffix(ZIP_SUFFIX)
-                        .tempfile_in(directory)
-                        .context("failed to create temp file in custom directory")?,
-                    None => tempfile::NamedTempFile::new()
-                        .context("failed to create temp file in system directory")?,
-                };
-
-                temp_file
-                    .write_all(zip_bytes)
+                // the archive gets its own directory, renaming it on upload stays inside
+                let temp_dir = artifacts::create_dir(ZIP_PREFIX, cfg)?;
+
+                let file_name = format!(
+                    "{}{}",
+                    path.file_name()
+                        .map(|n| n.to_string_lossy())
+                        .unwrap_or("archive".into()),
+                    ZIP_SUFFIX
+                );
+
+                std::fs::File::create(temp_dir.path().join(&file_name))
+                    .and_then(|mut file| file.write_all(zip_bytes))
                     .context("failed to write zip bytes to temp file")?;
 
-                let (_, file_path) = temp_file.keep().context("failed to persist temp file")?;
+                let file_path = temp_dir.keep().join(file_name);
 
                 Ok(HookContext::new(
                     file_path,
@@ -88,13 +100,7 @@ impl Hook for ZipHook {
                 let mut archive =
                     zip::read::ZipArchive::new(file).context("failed to read zip archive")?;
 
-                let temp_dir = match Self::base_temp_dir(cfg)? {
-                    Some(directory) => tempfile::Builder::new()
-                        .prefix(EXTRACT_PREFIX)
-                        .tempdir_in(directory)
-                        .context("failed to create temp dir in custom path")?,
-                    None => tempfile::tempdir().context("failed to create system temp dir")?,
-                };
+                let temp_dir = artifacts::create_dir(EXTRACT_PREFIX, cfg)?;
 
                 for i in 0..archive.len() {
                     let mut file = archive.by_index(i).context("failed to get file in zip")?;
@@ -125,4 +131,42 @@ impl Hook for ZipHook {
             }
         }
     }
+
+    fn explain(&self, ctx: &HookContext, _cfg: &AppConfig) -> anyhow::Result<Vec<String>> {
+        if self.exec == HookExecType::Pull {
+            return Ok(vec![format!(
+                "extracts the downloaded archive into {}*",
+                EXTRACT_PREFIX
+            )]);
+        }
+
+        let mut lines = vec![format!(
+            "compresses with zstd, level {}",
+            self.level
+                .map(|level| level.to_string())
+                .unwrap_or("default".into())
+        )];
+
+        if ctx.path.is_dir() {
+            let filter = PathFilter::for_root(&ctx.path_config, &ctx.path)
+                .context("failed to build path filter")?;
+
+            let (included, excluded) = self.partition_files(&ctx.path, &filter)?;
+
+            lines.push(format!(
+                "zips {} file(s), {} excluded by {:?}",
+                included.len(),
+                excluded.len(),
+                self.exclude.as_deref().unwrap_or_default()
+            ));
+            lines.extend(included.iter().map(|p| format!("+ {}", p.display())));
+            lines.extend(excluded.iter().map(|p| format!("- {}", p.display())));
+        } else if ctx.path.is_file() {
+            lines.push(format!("zips the file {}", ctx.path.display()));
+        } else {
+            lines.push(String::from("zips the content of the previous hook"));
+        }
+
+        Ok(lines)
+    }
 }
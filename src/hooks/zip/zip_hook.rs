@@ -1,6 +1,12 @@
-use crate::{config::app::AppConfig, hooks::zip::ZipHook, log_info};
+use crate::{
+    config::app::AppConfig, hooks::zip::ZipHook, log_debug, log_info, utils::filter::PathFilter,
+};
 use anyhow::Context;
-use std::{fs, io::Write, path::Path};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 impl ZipHook {
     pub fn build_exclude_set(&self) -> anyhow::Result<Option<globset::GlobSet>> {
@@ -21,18 +27,49 @@ impl ZipHook {
         }
     }
 
+    /// Splits the files under `path` into the ones zipped and the ones excluded by the globs.
+    ///
+    /// # Returns
+    /// Paths relative to `path`, included first.
+    pub fn partition_files(
+        &self,
+        path: &Path,
+        filter: &PathFilter,
+    ) -> anyhow::Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+        let exclude_set = self.build_exclude_set()?;
+        let mut included = Vec::new();
+        let mut excluded = Vec::new();
+
+        for entry in filter.walk(path) {
+            let relative_path = entry
+                .path()
+                .strip_prefix(path)
+                .context("failed to build relative path")?
+                .to_path_buf();
+
+            match exclude_set
+                .as_ref()
+                .is_some_and(|set| set.is_match(&relative_path))
+            {
+                true => excluded.push(relative_path),
+                false => included.push(relative_path),
+            }
+        }
+
+        Ok((included, excluded))
+    }
+
     pub fn process_directory(
         &self,
         path: &Path,
         zip: &mut zip::ZipWriter<std::io::Cursor<&mut Vec<u8>>>,
         options: zip::write::FileOptions<'_, ()>,
         exclude_set: Option<&globset::GlobSet>,
+        filter: &PathFilter,
     ) -> anyhow::Result<()> {
-        for entry in walkdir::WalkDir::new(path)
-            .into_iter()
-            .filter_map(Result::ok)
-            .filter(|e| e.file_type().is_file())
-        {
+        log_debug!("zipping directory with filter: {:?}", filter);
+
+        for entry in filter.walk(path) {
             let relative_path = entry
                 .path()
                 .strip_prefix(path)
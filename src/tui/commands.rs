@@ -23,6 +23,7 @@ pub enum RootMenuVariant {
 pub enum PathMenuVariant {
     Placeholder,
     List,
+    Status,
     Add,
     Remove,
 }
@@ -50,6 +51,7 @@ impl From<RootMenu> for TreeNodeRef<RootMenu> {
             .child(
                 TreeBuilder::new(RootMenu::Path(PathMenuVariant::Placeholder)).with_children(vec![
                     TreeBuilder::new(RootMenu::Path(PathMenuVariant::List)),
+                    TreeBuilder::new(RootMenu::Path(PathMenuVariant::Status)),
                     TreeBuilder::new(RootMenu::Path(PathMenuVariant::Add)),
                     TreeBuilder::new(RootMenu::Path(PathMenuVariant::Remove)),
                     TreeBuilder::new(RootMenu::Options(RootMenuOptions::Exit)),
@@ -88,6 +90,7 @@ impl std::fmt::Display for RootMenu {
             RootMenu::Path(variant) => match variant {
                 PathMenuVariant::Placeholder => write!(f, "Path Menu"),
                 PathMenuVariant::List => write!(f, "List Paths"),
+                PathMenuVariant::Status => write!(f, "Show Local and Remote Changes"),
                 PathMenuVariant::Add => write!(f, "Add Path"),
                 PathMenuVariant::Remove => write!(f, "Remove Path"),
             },
@@ -24,6 +24,7 @@ use_handlers! {
         (remote, add),
         (remote, remove),
         (remote, update),
+        (status, run),
         (sync, single),
         (sync, all)
     }
@@ -50,6 +51,17 @@ pub fn execute(context: CommandContext, action: &RootMenu) -> anyhow::Result<Exe
                     context.registry
                 ))?;
             }
+            PathMenuVariant::Status => {
+                status_run(command_context!(
+                    context.config,
+                    context.global,
+                    context.registry,
+                    StatusRunArgs {
+                        tags: &[],
+                        offline: &false,
+                    }
+                ))?;
+            }
             PathMenuVariant::Add => {
                 path_add(command_context!(
                     context.config,
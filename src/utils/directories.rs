@@ -6,8 +6,6 @@ use std::path::PathBuf;
 #[derive(Default)]
 pub struct Directories {
     pub config_dir: PathBuf,
-
-    #[allow(dead_code)]
     pub data_dir: PathBuf,
 }
 
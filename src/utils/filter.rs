@@ -0,0 +1,200 @@
+use crate::{
+    config::prelude::{HookConfig, PathConfig},
+    hooks::prelude::SqliteHook,
+};
+use anyhow::Context;
+use std::path::Path;
+
+pub const IGNORE_FILE: &str = ".rcloudignore";
+
+/// Include/exclude rules declared for a path, shared by hashing, zipping and rclone.
+///
+/// Excludes (the path's `exclude` list followed by the lines of `.rcloudignore`)
+/// use gitignore syntax:
+/// - `*.tmp` matches at any depth, `/build` and `docs/build` only from the root.
+/// - A trailing `/` (e.g. `node_modules/`) only matches directories.
+/// - `!pattern` re-includes a previously excluded path.
+///
+/// Includes are plain globs. When declared, files not matching them are skipped.
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    include_set: Option<globset::GlobSet>,
+    ignore: ignore::gitignore::Gitignore,
+}
+
+impl Default for PathFilter {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            include_set: None,
+            ignore: ignore::gitignore::Gitignore::empty(),
+        }
+    }
+}
+
+impl PathFilter {
+    pub fn new(include: Vec<String>, exclude: Vec<String>) -> anyhow::Result<Self> {
+        let mut builder = ignore::gitignore::GitignoreBuilder::new("");
+
+        for line in &exclude {
+            builder
+                .add_line(None, line)
+                .with_context(|| format!("invalid exclude pattern: {}", line))?;
+        }
+
+        Ok(Self {
+            include_set: Self::build_set(&include).context("failed to build include set")?,
+            ignore: builder.build().context("failed to build exclude rules")?,
+            include,
+            exclude,
+        })
+    }
+
+    /// Builds the filter for a path, rooted at its `local_path`.
+    pub fn from_path_config(path_config: &PathConfig) -> anyhow::Result<Self> {
+        Self::for_root(path_config, Path::new(&path_config.local_path))
+    }
+
+    /// Builds the filter for a path, reading the ignore file from `root`.
+    ///
+    /// `root` is usually `local_path`, but can be any directory holding the same
+    /// tree (e.g. a downloaded copy), so every code path applies the same rules.
+    /// The ignore file is `ignore_file` when declared, otherwise `.rcloudignore`.
+    /// With a Sqlite hook, the databases journal files are excluded as well.
+    pub fn for_root(path_config: &PathConfig, root: &Path) -> anyhow::Result<Self> {
+        let mut exclude = path_config.exclude.clone();
+
+        let ignore_file = root.join(path_config.ignore_file.as_deref().unwrap_or(IGNORE_FILE));
+
+        if ignore_file.is_file() {
+            let content = std::fs::read_to_string(&ignore_file)
+                .with_context(|| format!("failed to read ignore file: {:?}", ignore_file))?;
+
+            exclude.extend(
+                content
+                    .lines()
+                    .map(str::trim_end)
+                    .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+                    .map(String::from),
+            );
+        } else if path_config.ignore_file.is_some() && root.is_dir() {
+            anyhow::bail!("ignore file does not exists: {:?}", ignore_file);
+        }
+
+        for hook in path_config.hooks.push.iter().chain(&path_config.hooks.pull) {
+            if let HookConfig::Sqlite(config) = hook {
+                exclude.extend(SqliteHook::from(config.clone()).sidecar_patterns());
+            }
+        }
+
+        Self::new(path_config.include.clone(), exclude)
+    }
+
+    fn build_set(patterns: &[String]) -> anyhow::Result<Option<globset::GlobSet>> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+
+        let mut builder = globset::GlobSetBuilder::new();
+
+        for pattern in patterns {
+            let glob = match pattern.strip_prefix('/') {
+                Some(anchored) => anchored.to_string(),
+                None => format!("**/{}", pattern),
+            };
+
+            builder.add(
+                globset::Glob::new(&glob)
+                    .with_context(|| format!("invalid glob pattern: {}", pattern))?,
+            );
+        }
+
+        Ok(Some(builder.build().context("failed to build glob set")?))
+    }
+
+    /// Whether a path relative to the filtered root should be synced.
+    ///
+    /// Like git, nothing inside an excluded directory can be re-included.
+    /// Includes only apply to files, directories are only checked against excludes.
+    pub fn is_included(&self, relative_path: &Path, is_dir: bool) -> bool {
+        let parent_ignored = relative_path
+            .ancestors()
+            .skip(1)
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .any(|parent| self.ignore.matched(parent, true).is_ignore());
+
+        if parent_ignored || self.ignore.matched(relative_path, is_dir).is_ignore() {
+            return false;
+        }
+
+        match (&self.include_set, is_dir) {
+            (Some(set), false) => set.is_match(relative_path),
+            _ => true,
+        }
+    }
+
+    /// Walks every included file under `root`, skipping excluded directories entirely.
+    pub fn walk<'a>(&'a self, root: &'a Path) -> impl Iterator<Item = walkdir::DirEntry> + 'a {
+        walkdir::WalkDir::new(root)
+            .into_iter()
+            .filter_entry(move |entry| match entry.path().strip_prefix(root) {
+                Ok(relative) if !relative.as_os_str().is_empty() => {
+                    self.is_included(relative, entry.file_type().is_dir())
+                }
+                _ => true,
+            })
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+    }
+
+    /// Translates a gitignore pattern into rclone filter rules.
+    fn rclone_rules(line: &str) -> Vec<String> {
+        let (action, pattern) = match line.strip_prefix('!') {
+            Some(negated) => ('+', negated),
+            None => ('-', line.strip_prefix('\\').unwrap_or(line)),
+        };
+
+        let (pattern, dir_only) = match pattern.strip_suffix('/') {
+            Some(directory) => (directory, true),
+            None => (pattern, false),
+        };
+
+        let pattern = match pattern.strip_prefix("**/") {
+            Some(any_depth) => any_depth.to_string(),
+            None if !pattern.starts_with('/') && pattern.contains('/') => format!("/{}", pattern),
+            None => pattern.to_string(),
+        };
+
+        match dir_only {
+            true => vec![format!("{} {}/**", action, pattern)],
+            false => vec![
+                format!("{} {}", action, pattern),
+                format!("{} {}/**", action, pattern),
+            ],
+        }
+    }
+
+    /// Translates the rules into rclone `--filter` arguments.
+    ///
+    /// gitignore applies the last matching rule while rclone applies the first,
+    /// so excludes are emitted in reverse order.
+    pub fn rclone_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = self
+            .exclude
+            .iter()
+            .rev()
+            .flat_map(|line| Self::rclone_rules(line))
+            .map(|rule| format!("--filter={}", rule))
+            .collect();
+
+        if !self.include.is_empty() {
+            args.extend(self.include.iter().map(|p| format!("--filter=+ {}", p)));
+            args.push(String::from("--filter=- **"));
+        }
+
+        args
+    }
+}
@@ -1,29 +1,269 @@
+use crate::{
+    config::prelude::AppConfig,
+    log_warn,
+    utils::{
+        filter::PathFilter,
+        hash_cache::{HASH_CACHE_FILE, HashCache},
+        prelude::directories,
+    },
+};
 use anyhow::Context;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::Path;
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Version of the digest format produced by `Hash::hash_path`.
+///
+/// - `1`: file digests concatenated in walk order, relative paths not included.
+/// - `2`: entries sorted by relative path, each one contributing its path,
+///   optionally its mode, and its content digest.
+pub const HASH_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+    Xxh3,
+}
+
+impl std::fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HashAlgorithm::Sha256 => write!(f, "sha256"),
+            HashAlgorithm::Blake3 => write!(f, "blake3"),
+            HashAlgorithm::Xxh3 => write!(f, "xxh3"),
+        }
+    }
+}
+
+/// Incremental hasher over the supported algorithms, producing hex digests.
+enum Hasher {
+    Sha256(Sha256),
+    Blake3(Box<blake3::Hasher>),
+    Xxh3(Box<xxhash_rust::xxh3::Xxh3>),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashAlgorithm::Blake3 => Hasher::Blake3(Box::default()),
+            HashAlgorithm::Xxh3 => Hasher::Xxh3(Box::default()),
+        }
+    }
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Blake3(hasher) => {
+                hasher.update(data.as_ref());
+            }
+            Hasher::Xxh3(hasher) => hasher.update(data.as_ref()),
+        }
+    }
+
+    fn finalize(self) -> String {
+        match self {
+            Hasher::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Hasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            Hasher::Xxh3(hasher) => format!("{:032x}", hasher.digest128()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct HashOptions {
+    pub include_modes: bool,
+
+    pub algorithm: HashAlgorithm,
+
+    /// File used to persist per-file digests between runs, `None` disables the cache.
+    pub cache_path: Option<PathBuf>,
+}
+
+impl From<&AppConfig> for HashOptions {
+    fn from(config: &AppConfig) -> Self {
+        let data_dir = &directories().data_dir;
+
+        Self {
+            include_modes: config.core.hash_modes,
+            algorithm: config.core.hash_algorithm,
+            cache_path: match config.core.hash_cache && !data_dir.as_os_str().is_empty() {
+                true => Some(data_dir.join(HASH_CACHE_FILE)),
+                false => None,
+            },
+        }
+    }
+}
+
+impl HashOptions {
+    /// Disables the cache, e.g. for temporary trees that will not be hashed again.
+    pub fn without_cache(self) -> Self {
+        Self {
+            cache_path: None,
+            ..self
+        }
+    }
+}
 
 pub struct Hash;
 
 impl Hash {
-    fn hash_file(path: &Path) -> anyhow::Result<String> {
-        let content =
-            std::fs::read(path).with_context(|| format!("failed to read file: {:?}", path))?;
+    fn hash_file(path: &Path, algorithm: HashAlgorithm) -> anyhow::Result<String> {
+        let mut file = std::fs::File::open(path)
+            .with_context(|| format!("failed to open file: {:?}", path))?;
 
-        let mut hasher = Sha256::new();
-        hasher.update(&content);
+        let mut hasher = Hasher::new(algorithm);
+        let mut buffer = vec![0u8; READ_CHUNK_SIZE];
 
-        Ok(format!("{:x}", hasher.finalize()))
+        loop {
+            let read = file
+                .read(&mut buffer)
+                .with_context(|| format!("failed to read file: {:?}", path))?;
+
+            if read == 0 {
+                break;
+            }
+
+            hasher.update(&buffer[..read]);
+        }
+
+        Ok(hasher.finalize())
     }
 
-    fn hash_directory(path: &Path) -> anyhow::Result<String> {
-        let mut file_hashes = vec![];
+    /// Hashes every file in parallel, reusing cached digests of unchanged files.
+    fn hash_files(
+        files: &[PathBuf],
+        algorithm: HashAlgorithm,
+        mut cache: Option<&mut HashCache>,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut cached = Vec::with_capacity(files.len());
 
-        for entry in walkdir::WalkDir::new(path)
-            .into_iter()
-            .filter_map(Result::ok)
-            .filter(|e| e.file_type().is_file())
+        for file in files {
+            let lookup = match cache.as_deref_mut() {
+                Some(cache) => {
+                    let metadata = std::fs::metadata(file)
+                        .with_context(|| format!("failed to read metadata: {:?}", file))?;
+
+                    let digest = cache.get(file, &metadata, algorithm);
+                    Some((metadata, digest))
+                }
+                None => None,
+            };
+
+            cached.push(lookup);
+        }
+
+        let digests = files
+            .par_iter()
+            .zip(cached.par_iter())
+            .map(|(file, lookup)| match lookup {
+                Some((_, Some(digest))) => Ok(digest.clone()),
+                _ => Hash::hash_file(file, algorithm)
+                    .with_context(|| format!("failed to hash file: {:?}", file)),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        if let Some(cache) = cache {
+            for ((file, lookup), digest) in files.iter().zip(cached).zip(&digests) {
+                if let Some((metadata, None)) = lookup {
+                    cache.insert(file, &metadata, algorithm, digest);
+                }
+            }
+        }
+
+        Ok(digests)
+    }
+
+    #[cfg(unix)]
+    fn mode(path: &Path) -> anyhow::Result<u32> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let metadata = std::fs::metadata(path)
+            .with_context(|| format!("failed to read metadata: {:?}", path))?;
+
+        Ok(metadata.permissions().mode() & 0o7777)
+    }
+
+    #[cfg(not(unix))]
+    fn mode(path: &Path) -> anyhow::Result<u32> {
+        let metadata = std::fs::metadata(path)
+            .with_context(|| format!("failed to read metadata: {:?}", path))?;
+
+        Ok(metadata.permissions().readonly() as u32)
+    }
+
+    fn hash_directory(
+        path: &Path,
+        filter: &PathFilter,
+        options: &HashOptions,
+    ) -> anyhow::Result<String> {
+        let mut entries = vec![];
+
+        for entry in filter.walk(path) {
+            let relative_path = entry
+                .path()
+                .strip_prefix(path)
+                .context("failed to build relative path")?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            entries.push((relative_path, entry.into_path()));
+        }
+
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut cache = options.cache_path.as_deref().map(HashCache::load);
+
+        let (relative_paths, files): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+        let file_hashes = Hash::hash_files(&files, options.algorithm, cache.as_mut())?;
+
+        let mut hasher = Hasher::new(options.algorithm);
+
+        for ((relative_path, file_path), file_hash) in
+            relative_paths.iter().zip(&files).zip(file_hashes)
         {
-            let file_hash = Hash::hash_file(entry.path())
+            hasher.update(relative_path.as_bytes());
+            hasher.update([0]);
+
+            if options.include_modes {
+                hasher.update(Hash::mode(file_path)?.to_le_bytes());
+                hasher.update([0]);
+            }
+
+            hasher.update(file_hash.as_bytes());
+            hasher.update([b'\n']);
+        }
+
+        if let Some(cache) = cache.as_mut() {
+            cache.prune(path);
+
+            if let Err(e) = cache.save() {
+                log_warn!("failed to save hash cache: {}", e);
+            }
+        }
+
+        Ok(hasher.finalize())
+    }
+
+    /// Version 1 digest, kept to compare hashes stored before `HASH_VERSION` 2.
+    ///
+    /// Version 1 predates configurable algorithms and always uses SHA-256.
+    fn hash_directory_v1(path: &Path, filter: &PathFilter) -> anyhow::Result<String> {
+        let mut file_hashes = vec![];
+
+        for entry in filter.walk(path) {
+            let file_hash = Hash::hash_file(entry.path(), HashAlgorithm::Sha256)
                 .with_context(|| format!("failed to hash file: {:?}", entry.path()))?;
 
             file_hashes.push(file_hash);
@@ -37,17 +277,56 @@ impl Hash {
         Ok(format!("{:x}", hasher.finalize()))
     }
 
-    pub fn hash_path(path: &Path) -> anyhow::Result<String> {
+    pub fn hash_path(
+        path: &Path,
+        filter: &PathFilter,
+        options: &HashOptions,
+    ) -> anyhow::Result<String> {
         if !path.exists() {
             anyhow::bail!("path does not exists: {:?}", path);
         }
 
         match path.is_dir() {
-            true => Hash::hash_directory(path),
-            false => Hash::hash_file(path),
+            true => Hash::hash_directory(path, filter, options),
+            false => {
+                let file_hash = Hash::hash_file(path, options.algorithm)?;
+
+                match options.include_modes {
+                    true => {
+                        let mut hasher = Hasher::new(options.algorithm);
+                        hasher.update(format!("{}:{}", Hash::mode(path)?, file_hash));
+                        Ok(hasher.finalize())
+                    }
+                    false => Ok(file_hash),
+                }
+            }
+        }
+    }
+
+    /// Hashes `path` using the digest format of the given version.
+    pub fn hash_path_versioned(
+        path: &Path,
+        filter: &PathFilter,
+        options: &HashOptions,
+        version: u32,
+    ) -> anyhow::Result<String> {
+        match version {
+            HASH_VERSION => Hash::hash_path(path, filter, options),
+            1 => {
+                if !path.exists() {
+                    anyhow::bail!("path does not exists: {:?}", path);
+                }
+
+                match path.is_dir() {
+                    true => Hash::hash_directory_v1(path, filter),
+                    false => Hash::hash_file(path, HashAlgorithm::Sha256),
+                }
+            }
+            _ => anyhow::bail!("unsupported hash version: {}", version),
         }
     }
 
+    /// SHA-256 of `data`, independent of the configured algorithm.
     pub fn hash_bytes(data: &[u8]) -> String {
         let mut hasher = Sha256::new();
         hasher.update(data);
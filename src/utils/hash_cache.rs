@@ -0,0 +1,173 @@
+use crate::{log_debug, log_warn, utils::hash::HashAlgorithm};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+pub const HASH_CACHE_FILE: &str = "hash-cache.json";
+
+/// Files modified this recently are not cached, since a write in the same
+/// timestamp granularity would go unnoticed.
+const RACY_WINDOW_SECS: u64 = 2;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HashCacheEntry {
+    pub size: u64,
+    pub mtime_secs: u64,
+    pub mtime_nanos: u32,
+    pub inode: u64,
+
+    #[serde(default)]
+    pub algorithm: HashAlgorithm,
+
+    pub digest: String,
+}
+
+/// Persistent cache of file digests keyed by absolute path.
+///
+/// An entry is only reused when size, mtime and inode are unchanged and it was
+/// computed with the requested algorithm.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HashCache {
+    #[serde(skip)]
+    cache_path: PathBuf,
+
+    #[serde(skip)]
+    seen: HashSet<String>,
+
+    #[serde(default)]
+    entries: HashMap<String, HashCacheEntry>,
+}
+
+impl HashCache {
+    pub fn load(cache_path: &Path) -> Self {
+        let cache = match std::fs::read_to_string(cache_path) {
+            Ok(content) => serde_json::from_str::<HashCache>(&content).unwrap_or_else(|e| {
+                log_warn!("hash cache is corrupted, rebuilding it: {}", e);
+                HashCache::default()
+            }),
+            Err(_) => HashCache::default(),
+        };
+
+        log_debug!("hash cache loaded ({} entries)", cache.entries.len());
+
+        Self {
+            cache_path: cache_path.to_path_buf(),
+            ..cache
+        }
+    }
+
+    fn key(file: &Path) -> String {
+        file.to_string_lossy().to_string()
+    }
+
+    #[cfg(unix)]
+    fn inode(metadata: &std::fs::Metadata) -> u64 {
+        std::os::unix::fs::MetadataExt::ino(metadata)
+    }
+
+    #[cfg(not(unix))]
+    fn inode(_metadata: &std::fs::Metadata) -> u64 {
+        0
+    }
+
+    fn stamp(metadata: &std::fs::Metadata) -> Option<(u64, u32, u64)> {
+        let mtime = metadata
+            .modified()
+            .ok()?
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?;
+
+        Some((mtime.as_secs(), mtime.subsec_nanos(), Self::inode(metadata)))
+    }
+
+    /// Returns the cached digest of `file` if its metadata did not change.
+    pub fn get(
+        &mut self,
+        file: &Path,
+        metadata: &std::fs::Metadata,
+        algorithm: HashAlgorithm,
+    ) -> Option<String> {
+        let key = Self::key(file);
+        let (mtime_secs, mtime_nanos, inode) = Self::stamp(metadata)?;
+
+        self.seen.insert(key.clone());
+
+        self.entries
+            .get(&key)
+            .filter(|entry| {
+                entry.size == metadata.len()
+                    && entry.mtime_secs == mtime_secs
+                    && entry.mtime_nanos == mtime_nanos
+                    && entry.inode == inode
+                    && entry.algorithm == algorithm
+            })
+            .map(|entry| entry.digest.clone())
+    }
+
+    pub fn insert(
+        &mut self,
+        file: &Path,
+        metadata: &std::fs::Metadata,
+        algorithm: HashAlgorithm,
+        digest: &str,
+    ) {
+        let Some((mtime_secs, mtime_nanos, inode)) = Self::stamp(metadata) else {
+            return;
+        };
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        if now.saturating_sub(mtime_secs) < RACY_WINDOW_SECS {
+            return;
+        }
+
+        self.entries.insert(
+            Self::key(file),
+            HashCacheEntry {
+                size: metadata.len(),
+                mtime_secs,
+                mtime_nanos,
+                inode,
+                algorithm,
+                digest: digest.to_string(),
+            },
+        );
+    }
+
+    /// Drops entries under `root` that were not looked up since the cache was loaded.
+    pub fn prune(&mut self, root: &Path) {
+        let prefix = format!("{}{}", Self::key(root), std::path::MAIN_SEPARATOR);
+
+        self.entries
+            .retain(|key, _| !key.starts_with(&prefix) || self.seen.contains(key));
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let contents = serde_json::to_string(&self).context("failed to serialize hash cache")?;
+
+        let directory = self
+            .cache_path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+
+        // a unique temp file per save, concurrent processes never write into the same one
+        let mut temp_file = tempfile::NamedTempFile::new_in(directory)
+            .with_context(|| format!("failed to create temp file in {:?}", directory))?;
+
+        std::io::Write::write_all(&mut temp_file, contents.as_bytes())
+            .with_context(|| format!("failed to write hash cache: {:?}", temp_file.path()))?;
+
+        temp_file
+            .persist(&self.cache_path)
+            .with_context(|| format!("failed to replace hash cache: {:?}", self.cache_path))?;
+
+        Ok(())
+    }
+}
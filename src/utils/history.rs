@@ -0,0 +1,128 @@
+use crate::{config::prelude::HookExecType, log_warn};
+use anyhow::Context;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncStatus {
+    Success,
+    Unchanged,
+    Aborted,
+    Failed,
+}
+
+impl std::fmt::Display for SyncStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncStatus::Success => write!(f, "success"),
+            SyncStatus::Unchanged => write!(f, "unchanged"),
+            SyncStatus::Aborted => write!(f, "aborted"),
+            SyncStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// One sync attempt, stored as a line of `history.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: i64,
+    pub path_id: String,
+    pub local_path: String,
+    pub remote: String,
+    pub direction: HookExecType,
+    pub status: SyncStatus,
+
+    #[serde(default)]
+    pub hash: Option<String>,
+
+    #[serde(default)]
+    pub error: Option<String>,
+
+    #[serde(default)]
+    pub duration_ms: u64,
+}
+
+/// Append-only sync history in the data directory.
+#[derive(Debug, Clone)]
+pub struct History {
+    history_path: PathBuf,
+}
+
+impl History {
+    pub fn new(history_path: &Path) -> Self {
+        Self {
+            history_path: history_path.to_path_buf(),
+        }
+    }
+
+    pub fn append(&self, entry: &HistoryEntry) -> anyhow::Result<()> {
+        let mut line = serde_json::to_string(entry).context("failed to serialize history entry")?;
+        line.push('\n');
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.history_path)
+            .with_context(|| format!("failed to open history: {:?}", self.history_path))?;
+
+        file.lock_exclusive()
+            .context("failed to acquire lock on history")?;
+
+        file.write_all(line.as_bytes())
+            .context("failed to write history entry")?;
+
+        fs2::FileExt::unlock(&file).context("failed to release lock on history")?;
+
+        Ok(())
+    }
+
+    /// Reads every entry in chronological order, skipping malformed lines.
+    pub fn entries(&self) -> anyhow::Result<Vec<HistoryEntry>> {
+        if !self.history_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&self.history_path)
+            .with_context(|| format!("failed to read history: {:?}", self.history_path))?;
+
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str::<HistoryEntry>(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    log_warn!("skipping malformed history entry: {}", e);
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// Latest entry of every path, keyed by path ID.
+    pub fn last_by_path(&self) -> anyhow::Result<std::collections::HashMap<String, HistoryEntry>> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .map(|entry| (entry.path_id.clone(), entry))
+            .collect())
+    }
+
+    /// Latest successful or unchanged sync of every path, keyed by path ID.
+    pub fn last_synced_by_path(
+        &self,
+    ) -> anyhow::Result<std::collections::HashMap<String, HistoryEntry>> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .filter(|entry| matches!(entry.status, SyncStatus::Success | SyncStatus::Unchanged))
+            .map(|entry| (entry.path_id.clone(), entry))
+            .collect())
+    }
+}
@@ -1,5 +1,10 @@
 pub mod directories;
+pub mod filter;
 pub mod hash;
+pub mod hash_cache;
+pub mod history;
+pub mod host;
 pub mod logger;
 pub mod path;
 pub mod prelude;
+pub mod schedule;
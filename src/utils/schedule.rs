@@ -0,0 +1,129 @@
+use anyhow::Context;
+use chrono::{DateTime, Local, TimeDelta};
+
+/// Parses a duration like `6h`, `30m` or `1d12h` (units `s`, `m`, `h`, `d`, `w`).
+pub fn parse_interval(interval: &str) -> anyhow::Result<TimeDelta> {
+    let mut total = TimeDelta::zero();
+    let mut digits = String::new();
+
+    for c in interval.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+
+        let amount: i64 = digits
+            .parse()
+            .with_context(|| format!("invalid interval: {}", interval))?;
+
+        let delta = match c {
+            's' => TimeDelta::try_seconds(amount),
+            'm' => TimeDelta::try_minutes(amount),
+            'h' => TimeDelta::try_hours(amount),
+            'd' => TimeDelta::try_days(amount),
+            'w' => TimeDelta::try_weeks(amount),
+            _ => anyhow::bail!("invalid interval unit '{}' in: {}", c, interval),
+        };
+
+        total = delta
+            .and_then(|delta| total.checked_add(&delta))
+            .with_context(|| format!("interval is too large: {}", interval))?;
+
+        digits.clear();
+    }
+
+    if !digits.is_empty() || total <= TimeDelta::zero() {
+        anyhow::bail!("invalid interval: {}", interval);
+    }
+
+    Ok(total)
+}
+
+/// Parses a point in time: a duration ago (`7d`), a date (`2025-01-31`) or RFC 3339.
+pub fn parse_time(value: &str) -> anyhow::Result<DateTime<chrono::Utc>> {
+    let is_interval = value
+        .chars()
+        .all(|c| c.is_ascii_digit() || c.is_whitespace() || "smhdw".contains(c));
+
+    if is_interval {
+        return chrono::Utc::now()
+            .checked_sub_signed(parse_interval(value)?)
+            .with_context(|| format!("time is too far in the past: {}", value));
+    }
+
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return date
+            .and_hms_opt(0, 0, 0)
+            .and_then(|dt| dt.and_local_timezone(Local).earliest())
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+            .with_context(|| format!("invalid date: {}", value));
+    }
+
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .with_context(|| {
+            format!(
+                "invalid time (expected 7d, YYYY-MM-DD or RFC 3339): {}",
+                value
+            )
+        })
+}
+
+/// When a path should be synced, parsed from `PathConfig.schedule`.
+///
+/// Accepted formats:
+/// - Intervals: `every 6h`, `every 30m`, `every 1d12h` (units `s`, `m`, `h`, `d`, `w`).
+/// - Cron expressions with 5 fields (`0 3 * * *`) or nicknames (`@daily`, `@hourly`).
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    Every(TimeDelta),
+    Cron(Box<croner::Cron>),
+}
+
+impl std::str::FromStr for Schedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        match s.strip_prefix("every ") {
+            Some(interval) => Ok(Schedule::Every(parse_interval(interval.trim())?)),
+            None => Ok(Schedule::Cron(Box::new(
+                s.parse::<croner::Cron>()
+                    .with_context(|| format!("invalid cron expression: {}", s))?,
+            ))),
+        }
+    }
+}
+
+impl Schedule {
+    /// Next time the schedule fires after `last_run`.
+    ///
+    /// A path that never ran is due right away for intervals, and at the
+    /// current minute (if it matches) or the next match for cron expressions.
+    pub fn next_run(
+        &self,
+        last_run: Option<DateTime<Local>>,
+        now: DateTime<Local>,
+    ) -> anyhow::Result<DateTime<Local>> {
+        match self {
+            Schedule::Every(interval) => match last_run {
+                Some(last_run) => last_run
+                    .checked_add_signed(*interval)
+                    .context("next run is out of range"),
+                None => Ok(now),
+            },
+            Schedule::Cron(cron) => cron
+                .find_next_occurrence(&last_run.unwrap_or(now - TimeDelta::minutes(1)), false)
+                .context("failed to compute next cron occurrence"),
+        }
+    }
+
+    pub fn is_due(
+        &self,
+        last_run: Option<DateTime<Local>>,
+        now: DateTime<Local>,
+    ) -> anyhow::Result<bool> {
+        Ok(self.next_run(last_run, now)? <= now)
+    }
+}
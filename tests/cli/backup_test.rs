@@ -0,0 +1,108 @@
+use rcloud::{
+    cli::commands::backup::utils::replicas::{
+        Replica, replace_target, replica_content, select_replica,
+    },
+    hooks::backup::{BackupType, backup_hook::BackupHookReplica},
+};
+
+fn replica(backup_type: BackupType, timestamp: u64, replica_number: u32) -> Replica {
+    Replica {
+        backup_type,
+        location: String::from("backups"),
+        processed: false,
+        object_name: None,
+        replica: BackupHookReplica {
+            path: format!("backups/{}.{}", timestamp, replica_number).into(),
+            timestamp,
+            replica_number,
+        },
+    }
+}
+
+#[test]
+fn test_select_replica() {
+    let replicas = vec![
+        replica(BackupType::Remote, 300, 3),
+        replica(BackupType::Local, 300, 3),
+        replica(BackupType::Local, 200, 2),
+        replica(BackupType::Local, 100, 1),
+    ];
+
+    let by_number = select_replica(&replicas, Some(3), None).expect("replica 3");
+    assert_eq!(by_number.backup_type, BackupType::Local);
+
+    let at = select_replica(&replicas, None, Some(250)).expect("replica before 250");
+    assert_eq!(at.replica.replica_number, 2);
+
+    assert!(select_replica(&replicas, None, Some(50)).is_none());
+    assert!(select_replica(&replicas, Some(9), None).is_none());
+    assert!(select_replica(&replicas, None, None).is_none());
+}
+
+fn entries(path: &std::path::Path) -> anyhow::Result<Vec<String>> {
+    let mut entries: Vec<_> = std::fs::read_dir(path)?
+        .filter_map(Result::ok)
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    entries.sort();
+
+    Ok(entries)
+}
+
+#[test]
+fn test_replace_target_swaps_content() -> anyhow::Result<()> {
+    let content = tempfile::tempdir()?;
+    let parent = tempfile::tempdir()?;
+    let target = parent.path().join("data");
+
+    std::fs::write(content.path().join("restored.txt"), "restored")?;
+    std::fs::create_dir(&target)?;
+    std::fs::write(target.join("current.txt"), "current")?;
+
+    replace_target(content.path(), &target)?;
+
+    assert_eq!(entries(&target)?, ["restored.txt"]);
+    assert_eq!(entries(parent.path())?, ["data"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_failed_replace_target_keeps_current_content() -> anyhow::Result<()> {
+    let parent = tempfile::tempdir()?;
+    let target = parent.path().join("data");
+
+    std::fs::create_dir(&target)?;
+    std::fs::write(target.join("current.txt"), "current")?;
+
+    assert!(replace_target(&parent.path().join("missing"), &target).is_err());
+
+    assert_eq!(entries(&target)?, ["current.txt"]);
+    assert_eq!(entries(parent.path())?, ["data"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_remote_replica_content_keeps_one_entry_directory() -> anyhow::Result<()> {
+    let fetched = tempfile::tempdir()?;
+    let mut remote = replica(BackupType::Remote, 100, 1);
+    remote.object_name = Some(String::from("data"));
+
+    // the directory `data` holds a single entry
+    std::fs::create_dir(fetched.path().join("data"))?;
+    std::fs::write(fetched.path().join("data/only.txt"), "only")?;
+
+    assert_eq!(
+        replica_content(&remote, fetched.path())?,
+        fetched.path().join("data")
+    );
+
+    // older replicas hold the directory content at their root
+    let legacy = tempfile::tempdir()?;
+    std::fs::write(legacy.path().join("only.txt"), "only")?;
+
+    assert_eq!(replica_content(&remote, legacy.path())?, legacy.path());
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::hooks::common::mock_path;
+use rcloud::{PathConfig, cli::commands::sync::utils::mirror::stale_local_files};
+use std::{fs, path::PathBuf};
+
+#[test]
+fn test_stale_local_files_skips_excluded() -> anyhow::Result<()> {
+    let source = tempfile::tempdir()?;
+    let target = tempfile::tempdir()?;
+
+    fs::write(source.path().join("kept.txt"), "kept")?;
+    fs::write(target.path().join("kept.txt"), "kept")?;
+    fs::write(target.path().join("removed.txt"), "removed")?;
+    fs::write(target.path().join("local.log"), "ignored")?;
+    fs::create_dir(target.path().join("node_modules"))?;
+    fs::write(target.path().join("node_modules/dep.js"), "excluded")?;
+    fs::write(target.path().join(".rcloudignore"), "*.log\n")?;
+
+    let path_config = PathConfig {
+        local_path: target.path().to_string_lossy().to_string(),
+        exclude: vec![String::from("node_modules/"), String::from(".rcloudignore")],
+        ..mock_path()
+    };
+
+    let stale = stale_local_files(&path_config, source.path(), target.path())?;
+
+    assert_eq!(stale, [PathBuf::from("removed.txt")]);
+
+    Ok(())
+}
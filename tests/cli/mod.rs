@@ -0,0 +1,4 @@
+pub mod backup_test;
+pub mod mirror_test;
+pub mod schedule_test;
+pub mod watch_test;
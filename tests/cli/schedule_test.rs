@@ -0,0 +1,32 @@
+use rcloud::cli::commands::schedule::utils::ScheduleState;
+use std::fs;
+
+#[test]
+fn test_failed_run_keeps_last_run() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let state_path = temp_dir.path().join("schedule-state.json");
+
+    let mut state = ScheduleState::load(&state_path)?;
+    state.record_failure("path", String::from("remote unreachable"))?;
+
+    let mut state = ScheduleState::load(&state_path)?;
+    assert!(state.last_run("path").is_none());
+    assert!(state.failed_at("path").is_some());
+
+    state.record_success("path")?;
+    let last_run = state.last_run("path");
+    state.record_failure("path", String::from("remote unreachable"))?;
+
+    let state = ScheduleState::load(&state_path)?;
+    assert!(last_run.is_some());
+    assert_eq!(state.last_run("path"), last_run);
+    assert_eq!(
+        state.runs["path"].last_error.as_deref(),
+        Some("remote unreachable")
+    );
+
+    // saved through a temp file renamed over the state, nothing is left aside
+    assert_eq!(fs::read_dir(temp_dir.path())?.count(), 1);
+
+    Ok(())
+}
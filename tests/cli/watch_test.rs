@@ -0,0 +1,18 @@
+use rcloud::cli::commands::watch::utils::Debouncer;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_debouncer_waits_for_quiet_period() {
+    let mut debouncer = Debouncer::new(Duration::from_secs(5));
+    let start = Instant::now();
+
+    debouncer.touch("a", start);
+    debouncer.touch("b", start);
+    debouncer.touch("a", start + Duration::from_secs(3));
+
+    assert!(debouncer.due(start + Duration::from_secs(4)).is_empty());
+    assert_eq!(debouncer.due(start + Duration::from_secs(5)), vec!["b"]);
+    assert!(debouncer.is_pending("a"));
+    assert_eq!(debouncer.due(start + Duration::from_secs(8)), vec!["a"]);
+    assert!(!debouncer.is_pending("a"));
+}
@@ -0,0 +1 @@
+pub mod rclone_test;
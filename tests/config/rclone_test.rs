@@ -0,0 +1,39 @@
+use crate::hooks::common::{mock_path, mock_remote};
+use rcloud::{AppConfig, config::rclone::RclonePreset};
+
+fn args(values: &[&str]) -> Option<Vec<String>> {
+    Some(values.iter().map(|v| v.to_string()).collect())
+}
+
+#[test]
+fn test_rclone_args_precedence() -> anyhow::Result<()> {
+    let mut config = AppConfig::default();
+    config.rclone.presets.insert(
+        String::from("slow-link"),
+        RclonePreset {
+            args: vec![String::from("--bwlimit=1M")],
+        },
+    );
+    config.core.rclone_args = args(&["--transfers=8"]);
+
+    let mut remote = mock_remote();
+    remote.rclone_preset = Some(String::from("slow-link"));
+    remote.rclone_args = args(&["--transfers=4"]);
+
+    let mut path = mock_path();
+    path.rclone_args = args(&["--transfers=2"]);
+
+    // rclone keeps the last value of a repeated flag, so the path wins over the remote and core
+    assert_eq!(
+        config.rclone_args(&remote, &path)?,
+        args(&[
+            "--transfers=8",
+            "--bwlimit=1M",
+            "--transfers=4",
+            "--transfers=2"
+        ])
+        .unwrap()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use crate::hooks::common::{mock_path, mock_remote};
+use rcloud::{
+    AppConfig, ChunkHookConfig, HookConfig, HookContext, HookExecType, ZipHookConfig,
+    cli::commands::sync::utils::execute_hooks,
+    hooks::artifacts::{self, artifact_root, stale_artifacts},
+};
+use std::{fs, path::Path, time::Duration};
+
+fn app_config(temp_path: &Path) -> AppConfig {
+    let mut config = AppConfig::default();
+    config.core.temp_path = Some(temp_path.to_path_buf());
+    config
+}
+
+fn push_hooks(part_size: Option<&str>) -> Vec<HookConfig> {
+    vec![
+        HookConfig::Zip(ZipHookConfig {
+            exec: HookExecType::Push,
+            level: None,
+            exclude: None,
+            when: None,
+        }),
+        HookConfig::Chunk(ChunkHookConfig {
+            exec: HookExecType::Push,
+            part_size: part_size.map(String::from),
+            when: None,
+        }),
+    ]
+}
+
+fn entries(path: &Path) -> anyhow::Result<Vec<String>> {
+    let mut entries: Vec<_> = fs::read_dir(path)?
+        .filter_map(Result::ok)
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    entries.sort();
+
+    Ok(entries)
+}
+
+#[test]
+fn test_hook_run_removes_artifacts() -> anyhow::Result<()> {
+    let source = tempfile::tempdir()?;
+    let temp_root = tempfile::tempdir()?;
+    let config = app_config(temp_root.path());
+
+    fs::write(source.path().join("notes.txt"), "hello")?;
+
+    let run = execute_hooks(
+        HookContext::new(
+            source.path().to_path_buf(),
+            "rclone",
+            &mock_remote(),
+            &mock_path(),
+        ),
+        &push_hooks(Some("1K")),
+        &config,
+    )?;
+
+    assert!(run.context.path.join("index.json").exists());
+    assert_eq!(run.artifacts.paths().len(), 2);
+    assert_eq!(
+        artifact_root(&run.context.path, &config).as_deref(),
+        run.artifacts.paths().last().map(|p| p.as_path())
+    );
+
+    let zip_dir = &run.artifacts.paths()[0];
+    assert!(
+        zip_dir
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("rcloud-zip-")
+    );
+
+    drop(run);
+
+    assert!(entries(temp_root.path())?.is_empty());
+    assert_eq!(entries(source.path())?, ["notes.txt"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_failed_hook_run_removes_artifacts() -> anyhow::Result<()> {
+    let source = tempfile::tempdir()?;
+    let temp_root = tempfile::tempdir()?;
+    let config = app_config(temp_root.path());
+
+    fs::write(source.path().join("notes.txt"), "hello")?;
+
+    // the Chunk hook fails without a part size, after the Zip hook created its archive
+    let result = execute_hooks(
+        HookContext::new(
+            source.path().to_path_buf(),
+            "rclone",
+            &mock_remote(),
+            &mock_path(),
+        ),
+        &push_hooks(None),
+        &config,
+    );
+
+    assert!(result.is_err());
+    assert!(entries(temp_root.path())?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_stale_artifacts() -> anyhow::Result<()> {
+    let temp_root = tempfile::tempdir()?;
+    let config = app_config(temp_root.path());
+
+    fs::create_dir(temp_root.path().join("rcloud-extract-abc"))?;
+    fs::write(temp_root.path().join("rcloud-extract-abc/a.txt"), "12345")?;
+    fs::create_dir(temp_root.path().join("unrelated"))?;
+
+    let stale = stale_artifacts(&config, Duration::ZERO)?;
+
+    assert_eq!(stale.len(), 1);
+    assert_eq!(stale[0].path, temp_root.path().join("rcloud-extract-abc"));
+    assert_eq!(stale[0].size, 5);
+
+    assert!(stale_artifacts(&config, Duration::from_secs(3600))?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_stale_artifacts_skips_in_use() -> anyhow::Result<()> {
+    let temp_root = tempfile::tempdir()?;
+    let config = app_config(temp_root.path());
+
+    let dir = artifacts::create_dir("rcloud-extract-", &config)?;
+
+    assert!(stale_artifacts(&config, Duration::ZERO)?.is_empty());
+
+    let kept = dir.keep();
+
+    assert!(stale_artifacts(&config, Duration::ZERO)?.is_empty());
+
+    artifacts::remove(&kept)?;
+
+    assert!(entries(temp_root.path())?.is_empty());
+
+    Ok(())
+}
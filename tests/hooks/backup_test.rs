@@ -0,0 +1,264 @@
+use crate::hooks::common::mock_remote;
+use chrono::{Local, TimeDelta, TimeZone};
+use rcloud::hooks::backup::{
+    SnapshotMode,
+    backup_hook::BackupHookReplica,
+    incremental::{CHUNKS_DIR, ChunkStore, local, remote},
+    retention::{RetentionPolicy, parse_size},
+    utils::{self, snapshot::Snapshot},
+};
+use std::fs;
+
+fn daily_replicas(now: chrono::DateTime<Local>, days: i64) -> Vec<BackupHookReplica> {
+    (0..days)
+        .map(|day| BackupHookReplica {
+            path: std::path::PathBuf::new(),
+            timestamp: (now - TimeDelta::days(day)).timestamp() as u64,
+            replica_number: (days - day) as u32,
+        })
+        .collect()
+}
+
+#[test]
+fn test_backup_retention() -> anyhow::Result<()> {
+    let now = Local
+        .with_ymd_and_hms(2025, 3, 15, 12, 0, 0)
+        .single()
+        .expect("valid date");
+
+    let utc_now = now.to_utc();
+    let replicas = daily_replicas(now, 60);
+
+    let count_only = RetentionPolicy::default();
+    let pruned = count_only.select_prunable(&replicas, 3, utc_now, |_| Ok(0))?;
+    assert_eq!(pruned.len(), 57);
+    assert!(!pruned.contains(&0) && !pruned.contains(&2));
+
+    let gfs = RetentionPolicy {
+        keep_daily: 7,
+        keep_monthly: 3,
+        ..Default::default()
+    };
+    let pruned = gfs.select_prunable(&replicas, 1, utc_now, |_| Ok(0))?;
+    assert_eq!(replicas.len() - pruned.len(), 9);
+    assert_eq!(pruned.first(), Some(&59));
+
+    let max_age = RetentionPolicy {
+        keep_daily: 30,
+        max_age: Some(String::from("10d")),
+        ..Default::default()
+    };
+    let pruned = max_age.select_prunable(&replicas, 1, utc_now, |_| Ok(0))?;
+    assert_eq!(replicas.len() - pruned.len(), 11);
+
+    let max_size = RetentionPolicy {
+        keep_daily: 30,
+        max_size: Some(String::from("1K")),
+        ..Default::default()
+    };
+    let pruned = max_size.select_prunable(&replicas, 1, utc_now, |_| Ok(300))?;
+    assert_eq!(replicas.len() - pruned.len(), 3);
+
+    let too_small = RetentionPolicy {
+        max_size: Some(String::from("1")),
+        ..Default::default()
+    };
+    let pruned = too_small.select_prunable(&replicas, 5, utc_now, |_| Ok(300))?;
+    assert!(!pruned.contains(&0));
+
+    assert_eq!(parse_size("10G")?, 10 << 30);
+    assert_eq!(parse_size("500MiB")?, 500 << 20);
+    assert!(parse_size("ten").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_backup_incremental() -> anyhow::Result<()> {
+    let source_dir = tempfile::tempdir()?;
+    let backup_dir = tempfile::tempdir()?;
+    let restore_dir = tempfile::tempdir()?;
+
+    let source = source_dir.path().join("data");
+    let root = backup_dir.path().to_string_lossy().to_string();
+    let store = ChunkStore::new(backup_dir.path());
+
+    fs::create_dir_all(source.join("nested"))?;
+    fs::write(source.join("unchanged.txt"), "same content")?;
+    fs::write(source.join("nested/changed.txt"), "first version")?;
+
+    local::create_local_incremental_backup(&source, &root, 1)?;
+    assert_eq!(store.digests()?.len(), 2);
+
+    fs::write(source.join("nested/changed.txt"), "second version")?;
+
+    local::create_local_incremental_backup(&source, &root, 2)?;
+    assert_eq!(store.digests()?.len(), 3);
+
+    let mut replicas = local::get_local_incremental_replicas(&root)?;
+    replicas.sort_by_key(|r| r.replica_number);
+    assert_eq!(replicas.len(), 2);
+
+    let restored =
+        local::restore_local_incremental_replica(&replicas[0], &root, restore_dir.path())?;
+    assert_eq!(restored, restore_dir.path().join("data"));
+    assert_eq!(
+        fs::read_to_string(restored.join("nested/changed.txt"))?,
+        "first version"
+    );
+    assert_eq!(
+        fs::read_to_string(restored.join("unchanged.txt"))?,
+        "same content"
+    );
+
+    let pruned = local::rotate_local_incremental_replicas(
+        replicas,
+        1,
+        &RetentionPolicy::default(),
+        false,
+        &root,
+    )?;
+    assert_eq!(pruned.len(), 1);
+    assert_eq!(pruned[0].replica_number, 1);
+    assert_eq!(store.digests()?.len(), 2);
+    assert!(backup_dir.path().join(CHUNKS_DIR).is_dir());
+
+    Ok(())
+}
+
+#[test]
+fn test_backup_remote_object() -> anyhow::Result<()> {
+    let path: rcloud::PathConfig = serde_json::from_value(serde_json::json!({
+        "id": "p1",
+        "remote_id": "r1",
+        "local_path": "/tmp/data",
+        "remote_path": "backups/data",
+        "hooks": {
+            "push": [{ "type": "zip", "exec": "Push", "level": null, "exclude": null }],
+            "pull": [],
+        },
+    }))?;
+
+    assert_eq!(
+        path.remote_object(path.hooks.by_exec(rcloud::HookExecType::Push)),
+        "backups/data/data.zip"
+    );
+    assert_eq!(
+        path.remote_object(path.hooks.by_exec(rcloud::HookExecType::Pull)),
+        "backups/data"
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_backup_snapshot_hardlink() -> anyhow::Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let source = tempfile::tempdir()?;
+    let backup = tempfile::tempdir()?;
+
+    let first = backup.path().join("1");
+    let second = backup.path().join("2");
+
+    fs::create_dir_all(source.path().join("nested"))?;
+    fs::write(source.path().join("unchanged.txt"), "same content")?;
+    fs::write(source.path().join("nested/changed.txt"), "first version")?;
+
+    Snapshot::new(SnapshotMode::Hardlink, None).create(source.path(), &first)?;
+
+    fs::write(source.path().join("nested/changed.txt"), "second version")?;
+
+    Snapshot::new(SnapshotMode::Hardlink, Some(&first)).create(source.path(), &second)?;
+
+    let inode = |path: std::path::PathBuf| fs::metadata(path).map(|m| m.ino());
+
+    assert_eq!(
+        inode(first.join("unchanged.txt"))?,
+        inode(second.join("unchanged.txt"))?
+    );
+    assert_ne!(
+        inode(first.join("nested/changed.txt"))?,
+        inode(second.join("nested/changed.txt"))?
+    );
+    assert_eq!(
+        fs::read_to_string(first.join("nested/changed.txt"))?,
+        "first version"
+    );
+    assert_eq!(
+        fs::read_to_string(second.join("nested/changed.txt"))?,
+        "second version"
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_backup_max_size_counts_hardlinks_once() -> anyhow::Result<()> {
+    let backup = tempfile::tempdir()?;
+    let content = "x".repeat(1000);
+    let mut replicas = Vec::new();
+
+    for number in 1..=3u32 {
+        let path = backup.path().join(format!("{}.{}", 100 * number, number));
+        fs::create_dir(&path)?;
+
+        match number {
+            1 => fs::write(path.join("data.bin"), &content)?,
+            _ => fs::hard_link(backup.path().join("100.1/data.bin"), path.join("data.bin"))?,
+        }
+
+        replicas.push(BackupHookReplica {
+            path,
+            timestamp: 100 * number as u64,
+            replica_number: number,
+        });
+    }
+
+    let retention = RetentionPolicy {
+        max_size: Some(String::from("1500")),
+        ..Default::default()
+    };
+
+    // the three replicas share a single 1000 bytes file
+    let pruned = utils::rotate_local_replicas(replicas, 3, &retention, true)?;
+    assert!(pruned.is_empty());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_remote_incremental_listing_errors() -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = tempfile::tempdir()?;
+    let rclone = temp_dir.path().join("rclone");
+    let rclone_path = rclone.to_string_lossy().to_string();
+
+    // a missing manifests directory means there are no replicas yet
+    fs::write(
+        &rclone,
+        "#!/bin/sh\necho 'directory not found' >&2\nexit 3\n",
+    )?;
+    fs::set_permissions(&rclone, fs::Permissions::from_mode(0o755))?;
+
+    assert!(
+        remote::get_remote_incremental_replicas("backups", &rclone_path, &mock_remote())?
+            .is_empty()
+    );
+
+    // any other failure must not be mistaken for an empty remote
+    fs::write(
+        &rclone,
+        "#!/bin/sh\necho 'authentication failed' >&2\nexit 1\n",
+    )?;
+
+    assert!(
+        remote::get_remote_incremental_replicas("backups", &rclone_path, &mock_remote()).is_err()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use crate::hooks::common::{mock_path, mock_remote};
+use rcloud::{
+    AppConfig, ChunkHook, ChunkHookConfig, Hook, HookConfig, HookContext, HookExecType,
+    ZipHookConfig,
+    config::remote_filename::{compute_remote_filename, is_multipart},
+};
+use std::fs;
+
+fn chunk_config(exec: HookExecType, part_size: Option<&str>) -> ChunkHookConfig {
+    ChunkHookConfig {
+        exec,
+        part_size: part_size.map(String::from),
+        when: None,
+    }
+}
+
+#[test]
+fn test_chunk_split_join() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let artifact = temp_dir.path().join("notes.zip");
+    let content: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(&artifact, &content)?;
+
+    let ctx = HookContext::new(artifact.clone(), "rclone", &mock_remote(), &mock_path());
+    let pushed = ChunkHook::from(chunk_config(HookExecType::Push, Some("4K")))
+        .process(ctx, &AppConfig::default())?;
+
+    assert!(pushed.path.is_dir());
+    assert_eq!(
+        pushed.path.file_name().and_then(|n| n.to_str()),
+        Some("notes.zip.parts")
+    );
+
+    let mut parts: Vec<_> = fs::read_dir(&pushed.path)?
+        .filter_map(Result::ok)
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    parts.sort();
+
+    assert_eq!(parts, ["index.json", "part-0001", "part-0002", "part-0003"]);
+    assert_eq!(fs::metadata(pushed.path.join("part-0001"))?.len(), 4096);
+    assert_eq!(
+        fs::metadata(pushed.path.join("part-0003"))?.len(),
+        10_000 - 8192
+    );
+
+    let pull = ChunkHook::from(chunk_config(HookExecType::Pull, None));
+    let ctx = HookContext::new(pushed.path.clone(), "rclone", &mock_remote(), &mock_path());
+
+    let pulled = pull.process(ctx.clone(), &AppConfig::default())?;
+
+    assert_eq!(
+        pulled.path.file_name().and_then(|n| n.to_str()),
+        Some("notes.zip")
+    );
+    assert_eq!(fs::read(&pulled.path)?, content);
+
+    let mut corrupted = fs::read(pushed.path.join("part-0002"))?;
+    corrupted[0] ^= 0xFF;
+    fs::write(pushed.path.join("part-0002"), corrupted)?;
+
+    assert!(pull.process(ctx.clone(), &AppConfig::default()).is_err());
+
+    fs::remove_file(pushed.path.join("part-0002"))?;
+
+    assert!(pull.process(ctx, &AppConfig::default()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_chunk_requires_file() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let ctx = HookContext::new(
+        temp_dir.path().to_path_buf(),
+        "rclone",
+        &mock_remote(),
+        &mock_path(),
+    );
+
+    let result = ChunkHook::from(chunk_config(HookExecType::Push, Some("1M")))
+        .process(ctx, &AppConfig::default());
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_chunk_remote_filename() {
+    let zip = HookConfig::Zip(ZipHookConfig {
+        exec: HookExecType::Push,
+        level: None,
+        exclude: None,
+        when: None,
+    });
+    let chunk = HookConfig::Chunk(chunk_config(HookExecType::Push, Some("100M")));
+
+    let hooks = vec![zip.clone(), chunk.clone()];
+    assert_eq!(compute_remote_filename(&hooks, "notes"), "notes.zip.parts");
+    assert!(is_multipart(&hooks));
+
+    let hooks = vec![chunk];
+    assert_eq!(compute_remote_filename(&hooks, "notes"), "notes.parts");
+
+    let hooks = vec![zip];
+    assert_eq!(compute_remote_filename(&hooks, "notes"), "notes.zip");
+    assert!(!is_multipart(&hooks));
+}
+
+#[test]
+fn test_chunk_explain() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let artifact = temp_dir.path().join("notes.zip");
+    fs::write(&artifact, vec![0u8; 2500])?;
+
+    let hook = ChunkHook::from(chunk_config(HookExecType::Push, Some("1K")));
+
+    let ctx = HookContext::new(artifact, "rclone", &mock_remote(), &mock_path());
+    assert_eq!(
+        hook.explain(&ctx, &AppConfig::default())?,
+        ["splits 2500 bytes into 3 part(s) of up to 1024 bytes"]
+    );
+
+    let ctx = HookContext::new(
+        temp_dir.path().join("missing.zip"),
+        "rclone",
+        &mock_remote(),
+        &mock_path(),
+    );
+    assert_eq!(
+        hook.explain(&ctx, &AppConfig::default())?,
+        ["splits the archive into parts of up to 1024 bytes"]
+    );
+    assert_eq!(fs::read_dir(temp_dir.path())?.count(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use crate::hooks::common::{mock_path, mock_remote};
+use rcloud::{
+    AppConfig, CommandHook, CommandHookConfig, Hook, HookContext, HookContextMetadata,
+    HookExecType, hooks::command::FailurePolicy,
+};
+use std::fs;
+
+fn command_hook(
+    command: &str,
+    timeout_secs: Option<u64>,
+    on_failure: FailurePolicy,
+) -> CommandHook {
+    CommandHook::from(CommandHookConfig {
+        exec: HookExecType::Push,
+        command: command.to_string(),
+        timeout_secs,
+        on_failure,
+        after_sync: false,
+        when: None,
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn test_command_env() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let output = temp_dir.path().join("env.txt");
+
+    let ctx = HookContext::new(
+        temp_dir.path().to_path_buf(),
+        "rclone",
+        &mock_remote(),
+        &mock_path(),
+    )
+    .with_metadata(HookContextMetadata::CalculatedHash, "abc123");
+
+    let hook = command_hook(
+        &format!(
+            "echo \"$RCLOUD_DIRECTION $RCLOUD_REMOTE_NAME $RCLOUD_HASH\" > {:?}",
+            output
+        ),
+        None,
+        FailurePolicy::Abort,
+    );
+
+    let result = hook.process(ctx, &AppConfig::default())?;
+
+    assert_eq!(result.path, temp_dir.path());
+    assert_eq!(fs::read_to_string(&output)?.trim(), "push drive abc123");
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_command_failure_policy() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let ctx = HookContext::new(
+        temp_dir.path().to_path_buf(),
+        "rclone",
+        &mock_remote(),
+        &mock_path(),
+    );
+
+    assert!(
+        command_hook("exit 3", None, FailurePolicy::Abort)
+            .run(&ctx)
+            .is_err()
+    );
+    assert!(
+        command_hook("exit 3", None, FailurePolicy::Warn)
+            .run(&ctx)
+            .is_ok()
+    );
+    assert!(
+        command_hook("exit 3", None, FailurePolicy::Ignore)
+            .run(&ctx)
+            .is_ok()
+    );
+
+    let started = std::time::Instant::now();
+    let timed_out = command_hook("sleep 10", Some(1), FailurePolicy::Abort).run(&ctx);
+
+    assert!(timed_out.is_err());
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+    Ok(())
+}
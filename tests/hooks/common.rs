@@ -0,0 +1,36 @@
+use rcloud::{PathConfig, PathConfigHooks, Remote};
+
+pub fn mock_remote() -> Remote {
+    Remote {
+        id: String::new(),
+        remote_name: String::from("drive"),
+        provider: String::from("drive"),
+        rclone_args: None,
+        rclone_preset: None,
+    }
+}
+
+pub fn mock_path() -> PathConfig {
+    PathConfig {
+        id: String::new(),
+        remote_id: String::new(),
+        local_path: String::new(),
+        remote_path: String::new(),
+        hash: None,
+        hash_version: None,
+        hash_algorithm: None,
+        tags: vec![],
+        schedule: None,
+        mode: rcloud::SyncMode::Copy,
+        max_delete: None,
+        include: vec![],
+        exclude: vec![],
+        ignore_file: None,
+        hooks: PathConfigHooks {
+            push: vec![],
+            pull: vec![],
+        },
+        rclone_args: None,
+        rclone_preset: None,
+    }
+}
@@ -0,0 +1,143 @@
+use crate::hooks::common::{mock_path, mock_remote};
+use chrono::TimeZone;
+use rcloud::{
+    CommandHookConfig, HookConfig, HookContext, HookExecType, PathConfig, ZipHookConfig,
+    hooks::{
+        command::FailurePolicy,
+        condition::{Condition, ConditionContext, validate_when},
+    },
+    utils::history::{History, HistoryEntry, SyncStatus},
+};
+use std::fs;
+
+fn entry(timestamp: i64, direction: HookExecType, status: SyncStatus) -> HistoryEntry {
+    HistoryEntry {
+        timestamp,
+        path_id: String::from("p1"),
+        local_path: String::new(),
+        remote: String::new(),
+        direction,
+        status,
+        hash: None,
+        error: None,
+        duration_ms: 0,
+    }
+}
+
+fn evaluate(expression: &str, ctx: &ConditionContext) -> anyhow::Result<bool> {
+    expression.parse::<Condition>()?.evaluate(ctx)
+}
+
+#[test]
+fn test_condition_content_and_operators() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    fs::write(temp_dir.path().join("a.txt"), vec![0u8; 2048])?;
+    fs::write(temp_dir.path().join("b.txt"), "b")?;
+
+    let hook = HookContext::new(
+        temp_dir.path().to_path_buf(),
+        "rclone",
+        &mock_remote(),
+        &mock_path(),
+    );
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2025, 6, 1, 14, 30, 0)
+        .unwrap();
+    let history = History::new(&temp_dir.path().join("missing.jsonl"));
+    let ctx = ConditionContext::new(&hook, HookExecType::Push, history, now);
+
+    assert!(evaluate("file_count == 2", &ctx)?);
+    assert!(evaluate("file_count > 1 and size >= 2K", &ctx)?);
+    assert!(!evaluate("size > 1M", &ctx)?);
+    assert!(evaluate("direction == 'push' and hour == 14", &ctx)?);
+    assert!(evaluate("weekday in [\"sat\", \"sun\"]", &ctx)?);
+    assert!(evaluate("not (direction == \"pull\") or false", &ctx)?);
+    assert!(evaluate("\"work\" in \"workstation\"", &ctx)?);
+
+    assert!(evaluate("unknown == 1", &ctx).is_err());
+    assert!(evaluate("file_count", &ctx).is_err());
+    assert!(evaluate("direction > 1", &ctx).is_err());
+    assert!("file_count >".parse::<Condition>().is_err());
+    assert!("(first_sync_today".parse::<Condition>().is_err());
+    assert!("hostname == 'x' extra".parse::<Condition>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_condition_history() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let history = History::new(&temp_dir.path().join("history.jsonl"));
+
+    let hook = HookContext::new(
+        temp_dir.path().to_path_buf(),
+        "rclone",
+        &mock_remote(),
+        &PathConfig {
+            id: String::from("p1"),
+            ..mock_path()
+        },
+    );
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2025, 6, 1, 14, 0, 0)
+        .unwrap();
+    let yesterday = (now - chrono::TimeDelta::days(1)).timestamp();
+    let this_morning = (now - chrono::TimeDelta::hours(5)).timestamp();
+
+    let ctx = ConditionContext::new(&hook, HookExecType::Push, history.clone(), now);
+    assert!(evaluate("first_sync_today", &ctx)?);
+    assert!(evaluate("hours_since_last_sync > 1000", &ctx)?);
+
+    history.append(&entry(yesterday, HookExecType::Push, SyncStatus::Success))?;
+    history.append(&entry(this_morning, HookExecType::Push, SyncStatus::Failed))?;
+    history.append(&entry(
+        this_morning,
+        HookExecType::Pull,
+        SyncStatus::Success,
+    ))?;
+
+    let ctx = ConditionContext::new(&hook, HookExecType::Push, history.clone(), now);
+    assert!(evaluate("first_sync_today and syncs_today == 0", &ctx)?);
+    assert!(evaluate("hours_since_last_sync == 24", &ctx)?);
+
+    history.append(&entry(
+        this_morning,
+        HookExecType::Push,
+        SyncStatus::Success,
+    ))?;
+
+    let ctx = ConditionContext::new(&hook, HookExecType::Push, history, now);
+    assert!(!evaluate("first_sync_today", &ctx)?);
+    assert!(evaluate(
+        "syncs_today == 1 and hours_since_last_sync == 5",
+        &ctx
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn test_when_rejected_on_renaming_hooks() {
+    let when = Some(String::from("first_sync_today"));
+
+    let zip = HookConfig::Zip(ZipHookConfig {
+        exec: HookExecType::Push,
+        level: None,
+        exclude: None,
+        when: when.clone(),
+    });
+
+    let command = HookConfig::Command(CommandHookConfig {
+        exec: HookExecType::Push,
+        command: String::from("true"),
+        timeout_secs: None,
+        on_failure: FailurePolicy::Abort,
+        after_sync: false,
+        when,
+    });
+
+    assert!(validate_when(&zip).is_err());
+    assert!(validate_when(&command).is_ok());
+}
@@ -0,0 +1,129 @@
+use crate::hooks::common::{mock_path, mock_remote};
+use rcloud::{
+    AppConfig, ExternalHook, ExternalHookConfig, HookConfig, HookContext, HookContextMetadata,
+    HookExecType, config::remote_filename::compute_remote_filename,
+    hooks::external::hook::find_plugin_in,
+};
+use std::{fs, path::Path};
+
+fn external_config(extension: Option<&str>) -> ExternalHookConfig {
+    ExternalHookConfig {
+        exec: HookExecType::Push,
+        name: String::from("pack"),
+        options: Some(serde_json::json!({ "level": 3 })),
+        modifies_filename: extension.is_some(),
+        extension: extension.map(String::from),
+        when: None,
+    }
+}
+
+#[cfg(unix)]
+fn write_plugin(directory: &Path, name: &str, script: &str) -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = directory.join(format!("rcloud-hook-{}", name));
+    fs::write(&path, format!("#!/bin/sh\n{}", script))?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_external_plugin_protocol() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let plugins = temp_dir.path().join("bin");
+    let request = temp_dir.path().join("request.json");
+    let output = temp_dir.path().join("notes.pack");
+    fs::create_dir(&plugins)?;
+
+    write_plugin(
+        &plugins,
+        "pack",
+        &format!(
+            "cat > {:?}\necho packed > {:?}\necho '{{\"path\": {:?}, \"metadata\": {{\"zip_checksum\": \"xyz\", \"custom\": \"ignored\"}}}}'\n",
+            request, output, output
+        ),
+    )?;
+
+    let search_path = std::env::join_paths([temp_dir.path(), plugins.as_path()])?;
+    let plugin = find_plugin_in("pack", &search_path).expect("plugin should be found");
+    assert!(find_plugin_in("missing", &search_path).is_none());
+
+    let ctx = HookContext::new(
+        temp_dir.path().to_path_buf(),
+        "rclone",
+        &mock_remote(),
+        &mock_path(),
+    )
+    .with_metadata(HookContextMetadata::CalculatedHash, "abc123");
+
+    let hook = ExternalHook::from(external_config(Some("pack")));
+    let result = hook.invoke(&plugin, ctx, &AppConfig::default())?;
+
+    assert_eq!(result.path, output);
+    assert_eq!(
+        result.metadata.get(&HookContextMetadata::ZipChecksum),
+        Some(&String::from("xyz"))
+    );
+    assert_eq!(
+        result.metadata.get(&HookContextMetadata::CalculatedHash),
+        Some(&String::from("abc123"))
+    );
+
+    let sent: serde_json::Value = serde_json::from_str(&fs::read_to_string(&request)?)?;
+
+    assert_eq!(sent["version"], 1);
+    assert_eq!(sent["exec"], "Push");
+    assert_eq!(sent["options"]["level"], 3);
+    assert_eq!(sent["context"]["remote"]["remote_name"], "drive");
+    assert_eq!(sent["context"]["metadata"]["calculated_hash"], "abc123");
+    // the plugin left its output outside the work dir, which is removed
+    assert!(!Path::new(sent["work_dir"].as_str().unwrap_or_default()).exists());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_external_plugin_failure() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    write_plugin(temp_dir.path(), "fail", "exit 2\n")?;
+    write_plugin(temp_dir.path(), "garbage", "echo not json\n")?;
+    write_plugin(temp_dir.path(), "silent", "")?;
+
+    let ctx = HookContext::new(
+        temp_dir.path().to_path_buf(),
+        "rclone",
+        &mock_remote(),
+        &mock_path(),
+    );
+
+    let hook = ExternalHook::from(external_config(None));
+    let plugin = |name: &str| temp_dir.path().join(format!("rcloud-hook-{}", name));
+
+    assert!(
+        hook.invoke(&plugin("fail"), ctx.clone(), &AppConfig::default())
+            .is_err()
+    );
+    assert!(
+        hook.invoke(&plugin("garbage"), ctx.clone(), &AppConfig::default())
+            .is_err()
+    );
+
+    let unchanged = hook.invoke(&plugin("silent"), ctx, &AppConfig::default())?;
+    assert_eq!(unchanged.path, temp_dir.path());
+
+    Ok(())
+}
+
+#[test]
+fn test_external_remote_filename() {
+    let hooks = vec![HookConfig::External(external_config(Some("tar.gz")))];
+    assert_eq!(compute_remote_filename(&hooks, "notes"), "notes.tar.gz");
+    assert!(hooks[0].modifies_filename());
+
+    let hooks = vec![HookConfig::External(external_config(None))];
+    assert_eq!(compute_remote_filename(&hooks, "notes"), "notes");
+    assert!(!hooks[0].modifies_filename());
+}
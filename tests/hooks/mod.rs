@@ -1 +1,10 @@
+pub mod artifacts_test;
+pub mod backup_test;
+pub mod chunk_test;
+pub mod command_test;
+pub mod common;
+pub mod condition_test;
+pub mod external_test;
+pub mod sqlite_test;
+pub mod template_test;
 pub mod zip_test;
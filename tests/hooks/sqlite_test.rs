@@ -0,0 +1,84 @@
+use crate::hooks::common::{mock_path, mock_remote};
+use rcloud::{AppConfig, Hook, HookContext, HookExecType, SqliteHook, SqliteHookConfig};
+use std::fs;
+
+fn sqlite_hook(exec: HookExecType) -> SqliteHook {
+    SqliteHook::from(SqliteHookConfig {
+        exec,
+        extensions: None,
+        when: None,
+    })
+}
+
+fn create_database(path: &std::path::Path, rows: usize) -> anyhow::Result<rusqlite::Connection> {
+    let connection = rusqlite::Connection::open(path)?;
+    connection.pragma_update(None, "journal_mode", "WAL")?;
+    connection.execute("CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT)", [])?;
+
+    for i in 0..rows {
+        connection.execute(
+            "INSERT INTO notes (body) VALUES (?1)",
+            [format!("note {} {}", i, "x".repeat(200))],
+        )?;
+    }
+
+    Ok(connection)
+}
+
+#[test]
+fn test_sqlite_push_snapshot() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("app");
+    fs::create_dir(&source)?;
+
+    // kept open so the rows only live in the write-ahead log
+    let _live = create_database(&source.join("app.db"), 10)?;
+    assert!(source.join("app.db-wal").exists());
+
+    fs::write(source.join("notes.txt"), "hello")?;
+    fs::write(source.join("thumbs.db"), "not a database")?;
+
+    let ctx = HookContext::new(source.clone(), "rclone", &mock_remote(), &mock_path());
+    let result = sqlite_hook(HookExecType::Push).process(ctx, &AppConfig::default())?;
+
+    assert_ne!(result.path, source);
+    assert_eq!(result.path.file_name(), source.file_name());
+    assert_eq!(fs::read_to_string(result.path.join("notes.txt"))?, "hello");
+    assert_eq!(
+        fs::read_to_string(result.path.join("thumbs.db"))?,
+        "not a database"
+    );
+    assert!(!result.path.join("app.db-wal").exists());
+    assert!(!result.path.join("app.db-shm").exists());
+
+    let snapshot = rusqlite::Connection::open(result.path.join("app.db"))?;
+    let count: i64 = snapshot.query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0))?;
+    assert_eq!(count, 10);
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_pull_integrity_check() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let downloaded = temp_dir.path().join("app");
+    fs::create_dir(&downloaded)?;
+
+    let database = downloaded.join("app.db");
+    create_database(&database, 200)?.pragma_update(None, "journal_mode", "DELETE")?;
+
+    let hook = sqlite_hook(HookExecType::Pull);
+    let ctx = HookContext::new(downloaded.clone(), "rclone", &mock_remote(), &mock_path());
+
+    let result = hook.process(ctx.clone(), &AppConfig::default())?;
+    assert_eq!(result.path, downloaded);
+
+    let mut content = fs::read(&database)?;
+    let page = 4096;
+    content[page * 2..page * 3].fill(0xAB);
+    fs::write(&database, content)?;
+
+    assert!(hook.process(ctx, &AppConfig::default()).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use crate::hooks::common::{mock_path, mock_remote};
+use rcloud::{
+    AppConfig, Hook, HookContext, HookExecType, TemplateHook, TemplateHookConfig,
+    hooks::template::hook::{render, strip},
+};
+use std::{collections::BTreeMap, fs};
+
+fn mock_app_config(home: &str, hostname: &str) -> AppConfig {
+    let mut cfg = AppConfig::default();
+
+    cfg.template.variables.extend([
+        (String::from("home"), String::from(home)),
+        (String::from("hostname"), String::from(hostname)),
+        (String::from("user"), String::from("me")),
+        (String::from("email"), String::from("me@example.com")),
+    ]);
+
+    cfg
+}
+
+fn template_hook(exec: HookExecType) -> TemplateHook {
+    TemplateHook::from(TemplateHookConfig {
+        exec,
+        patterns: vec![String::from(".gitconfig"), String::from("*.toml")],
+        when: None,
+    })
+}
+
+#[test]
+fn test_template_strip_render() -> anyhow::Result<()> {
+    let variables = BTreeMap::from([
+        (String::from("RCLOUD_HOME"), String::from("/home/me")),
+        (String::from("RCLOUD_WORK"), String::from("/home/me/work")),
+        (String::from("RCLOUD_USER"), String::from("me")),
+    ]);
+
+    let content =
+        "path = /home/me/work/src\nhome = /home/me\nname = me\nsome = meme\nshell = $SHELL";
+    let stripped = strip(content, &variables)?;
+
+    assert_eq!(
+        stripped,
+        "path = ${RCLOUD_WORK}/src\nhome = ${RCLOUD_HOME}\nname = ${RCLOUD_USER}\nsome = meme\nshell = $SHELL"
+    );
+    assert_eq!(render(&stripped, &variables), content);
+
+    Ok(())
+}
+
+#[test]
+fn test_template_hook_roundtrip() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("dotfiles");
+    fs::create_dir_all(source.join("app"))?;
+
+    fs::write(
+        source.join(".gitconfig"),
+        "[user]\n\temail = me@example.com\n[core]\n\texcludesfile = /home/me/.gitignore\n",
+    )?;
+    fs::write(source.join("app/settings.toml"), "host = \"laptop\"\n")?;
+    fs::write(source.join("notes.txt"), "/home/me stays here\n")?;
+
+    let laptop = mock_app_config("/home/me", "laptop");
+    let ctx = HookContext::new(source.clone(), "rclone", &mock_remote(), &mock_path());
+
+    let pushed = template_hook(HookExecType::Push).process(ctx, &laptop)?;
+
+    assert_ne!(pushed.path, source);
+    assert_eq!(
+        fs::read_to_string(pushed.path.join(".gitconfig"))?,
+        "[user]\n\temail = ${RCLOUD_EMAIL}\n[core]\n\texcludesfile = ${RCLOUD_HOME}/.gitignore\n"
+    );
+    assert_eq!(
+        fs::read_to_string(pushed.path.join("app/settings.toml"))?,
+        "host = \"${RCLOUD_HOSTNAME}\"\n"
+    );
+    assert_eq!(
+        fs::read_to_string(pushed.path.join("notes.txt"))?,
+        "/home/me stays here\n"
+    );
+
+    // the local content is never modified
+    assert_eq!(
+        fs::read_to_string(source.join("app/settings.toml"))?,
+        "host = \"laptop\"\n"
+    );
+
+    let desktop = mock_app_config("/Users/me", "desktop");
+    let ctx = HookContext::new(pushed.path.clone(), "rclone", &mock_remote(), &mock_path());
+
+    let pulled = template_hook(HookExecType::Pull).process(ctx, &desktop)?;
+
+    assert_eq!(
+        fs::read_to_string(pulled.path.join(".gitconfig"))?,
+        "[user]\n\temail = me@example.com\n[core]\n\texcludesfile = /Users/me/.gitignore\n"
+    );
+    assert_eq!(
+        fs::read_to_string(pulled.path.join("app/settings.toml"))?,
+        "host = \"desktop\"\n"
+    );
+
+    Ok(())
+}
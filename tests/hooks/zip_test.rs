@@ -1,33 +1,8 @@
+use crate::hooks::common::{mock_path, mock_remote};
 use anyhow::Context;
-use rcloud::{
-    AppConfig, Hook, HookContext, HookContextMetadata, PathConfig, PathConfigHooks, Remote,
-    ZipHook, ZipHookConfig,
-};
+use rcloud::{AppConfig, Hook, HookContext, HookContextMetadata, ZipHook, ZipHookConfig};
 use std::fs;
 
-fn mock_remote() -> Remote {
-    Remote {
-        id: String::new(),
-        remote_name: String::from("drive"),
-        provider: String::from("drive"),
-    }
-}
-
-fn mock_path() -> PathConfig {
-    PathConfig {
-        id: String::new(),
-        remote_id: String::new(),
-        local_path: String::new(),
-        remote_path: String::new(),
-        hash: None,
-        tags: vec![],
-        hooks: PathConfigHooks {
-            push: vec![],
-            pull: vec![],
-        },
-    }
-}
-
 fn mock_app_config() -> AppConfig {
     AppConfig::default()
 }
@@ -43,6 +18,7 @@ fn test_zip_single_file() -> anyhow::Result<()> {
         exec: rcloud::HookExecType::Push,
         level: Some(6),
         exclude: None,
+        when: None,
     };
 
     let hook = ZipHook::from(config);
@@ -78,6 +54,7 @@ fn test_zip_directory() -> anyhow::Result<()> {
         exec: rcloud::HookExecType::Push,
         level: Some(6),
         exclude: None,
+        when: None,
     };
 
     let hook = ZipHook::from(config);
@@ -109,6 +86,7 @@ fn test_zip_with_exclusions() -> anyhow::Result<()> {
         exec: rcloud::HookExecType::Push,
         level: Some(6),
         exclude: Some(vec!["*.log".to_string()]),
+        when: None,
     };
 
     let hook = ZipHook::from(config);
@@ -126,3 +104,36 @@ fn test_zip_with_exclusions() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_zip_explain() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir().context("failed to create temp_dir")?;
+
+    fs::write(temp_dir.path().join("notes.txt"), "notes")?;
+    fs::write(temp_dir.path().join("debug.log"), "log")?;
+
+    let hook = ZipHook::from(ZipHookConfig {
+        exec: rcloud::HookExecType::Push,
+        level: Some(3),
+        exclude: Some(vec!["*.log".to_string()]),
+        when: None,
+    });
+
+    let ctx = HookContext::new(
+        temp_dir.path().to_path_buf(),
+        "",
+        &mock_remote(),
+        &mock_path(),
+    );
+
+    let lines = hook.explain(&ctx, &mock_app_config())?;
+
+    assert!(lines.contains(&String::from("zips 1 file(s), 1 excluded by [\"*.log\"]")));
+    assert!(lines.contains(&String::from("+ notes.txt")));
+    assert!(lines.contains(&String::from("- debug.log")));
+
+    // nothing was written next to the content
+    assert_eq!(fs::read_dir(temp_dir.path())?.count(), 2);
+
+    Ok(())
+}
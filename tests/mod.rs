@@ -1 +1,4 @@
+pub mod cli;
+pub mod config;
 pub mod hooks;
+pub mod utils;
@@ -0,0 +1,126 @@
+use crate::hooks::common::mock_path;
+use anyhow::Context;
+use rcloud::{
+    HookConfig, HookExecType, PathConfig, SqliteHookConfig,
+    utils::{
+        filter::PathFilter,
+        hash::{Hash, HashOptions},
+    },
+};
+use std::{fs, path::Path};
+
+#[test]
+fn test_filter_exclude_any_depth() -> anyhow::Result<()> {
+    let filter = PathFilter::new(
+        vec![],
+        vec!["*.tmp".to_string(), "node_modules/".to_string()],
+    )?;
+
+    assert!(filter.is_included(Path::new("src/main.rs"), false));
+    assert!(!filter.is_included(Path::new("file.tmp"), false));
+    assert!(!filter.is_included(Path::new("nested/dir/file.tmp"), false));
+    assert!(!filter.is_included(Path::new("node_modules/pkg/index.js"), false));
+    assert!(!filter.is_included(Path::new("web/node_modules/pkg/index.js"), false));
+
+    Ok(())
+}
+
+#[test]
+fn test_filter_anchored_negated_and_include() -> anyhow::Result<()> {
+    let filter = PathFilter::new(
+        vec!["*.md".to_string()],
+        vec![
+            "/drafts/".to_string(),
+            "*.md".to_string(),
+            "!README.md".to_string(),
+        ],
+    )?;
+
+    assert!(filter.is_included(Path::new("README.md"), false));
+    assert!(!filter.is_included(Path::new("docs/notes.md"), false));
+    assert!(!filter.is_included(Path::new("drafts/README.md"), false));
+    assert!(!filter.is_included(Path::new("main.rs"), false));
+
+    assert_eq!(
+        filter.rclone_args(),
+        vec![
+            "--filter=+ README.md",
+            "--filter=+ README.md/**",
+            "--filter=- *.md",
+            "--filter=- *.md/**",
+            "--filter=- /drafts/**",
+            "--filter=+ *.md",
+            "--filter=- **",
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_ignore_file_is_honored_by_hash() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+
+    fs::write(temp_dir.path().join(".rcloudignore"), "*.swp\ncache/\n")
+        .context("failed to write ignore file")?;
+    fs::write(temp_dir.path().join("notes.txt"), b"notes").context("failed to write file")?;
+
+    let path_config = PathConfig {
+        local_path: temp_dir.path().to_string_lossy().to_string(),
+        ..mock_path()
+    };
+    let filter = PathFilter::from_path_config(&path_config)?;
+    let before = Hash::hash_path(temp_dir.path(), &filter, &HashOptions::default())?;
+
+    fs::write(temp_dir.path().join(".notes.txt.swp"), b"swap").context("failed to write swap")?;
+    fs::create_dir(temp_dir.path().join("cache")).context("failed to create cache")?;
+    fs::write(temp_dir.path().join("cache/blob"), b"blob").context("failed to write blob")?;
+
+    let filter = PathFilter::from_path_config(&path_config)?;
+    assert_eq!(
+        before,
+        Hash::hash_path(temp_dir.path(), &filter, &HashOptions::default())?
+    );
+
+    let walked: Vec<_> = filter
+        .walk(temp_dir.path())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+
+    assert!(walked.contains(&"notes.txt".to_string()));
+    assert!(!walked.contains(&"blob".to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_sidecars_are_left_out_of_hash() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+
+    fs::write(temp_dir.path().join("app.db"), b"database").context("failed to write db")?;
+
+    let mut path_config = PathConfig {
+        local_path: temp_dir.path().to_string_lossy().to_string(),
+        ..mock_path()
+    };
+    path_config.hooks.push = vec![HookConfig::Sqlite(SqliteHookConfig {
+        exec: HookExecType::Push,
+        extensions: None,
+        when: None,
+    })];
+
+    let filter = PathFilter::from_path_config(&path_config)?;
+    let before = Hash::hash_path(temp_dir.path(), &filter, &HashOptions::default())?;
+
+    fs::write(temp_dir.path().join("app.db-wal"), b"wal").context("failed to write wal")?;
+    fs::write(temp_dir.path().join("app.db-shm"), b"shm").context("failed to write shm")?;
+
+    assert_eq!(
+        before,
+        Hash::hash_path(temp_dir.path(), &filter, &HashOptions::default())?
+    );
+    assert!(!filter.is_included(Path::new("app.db-wal"), false));
+    assert!(filter.is_included(Path::new("app.db"), false));
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use anyhow::Context;
+use rcloud::utils::{
+    filter::PathFilter,
+    hash::{HASH_VERSION, Hash, HashAlgorithm, HashOptions},
+};
+use std::fs;
+
+fn hash(path: &std::path::Path) -> anyhow::Result<String> {
+    Hash::hash_path(path, &PathFilter::default(), &HashOptions::default())
+}
+
+#[test]
+fn test_hash_changes_on_rename() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+
+    fs::write(temp_dir.path().join("a.txt"), b"content").context("failed to write file")?;
+    let before = hash(temp_dir.path())?;
+
+    fs::rename(temp_dir.path().join("a.txt"), temp_dir.path().join("b.txt"))
+        .context("failed to rename file")?;
+
+    assert_ne!(before, hash(temp_dir.path())?);
+
+    Ok(())
+}
+
+#[test]
+fn test_hash_is_independent_of_creation_order() -> anyhow::Result<()> {
+    let first = tempfile::tempdir().context("failed to create temp dir")?;
+    let second = tempfile::tempdir().context("failed to create temp dir")?;
+
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        fs::write(first.path().join(name), name).context("failed to write file")?;
+    }
+
+    for name in ["c.txt", "a.txt", "b.txt"] {
+        fs::write(second.path().join(name), name).context("failed to write file")?;
+    }
+
+    assert_eq!(hash(first.path())?, hash(second.path())?);
+
+    Ok(())
+}
+
+#[test]
+fn test_hash_legacy_version() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+
+    fs::write(temp_dir.path().join("a.txt"), b"content").context("failed to write file")?;
+
+    let filter = PathFilter::default();
+    let options = HashOptions::default();
+
+    let legacy = Hash::hash_path_versioned(temp_dir.path(), &filter, &options, 1)?;
+    let current = Hash::hash_path_versioned(temp_dir.path(), &filter, &options, HASH_VERSION)?;
+
+    assert_ne!(legacy, current);
+    assert_eq!(current, hash(temp_dir.path())?);
+    assert!(Hash::hash_path_versioned(temp_dir.path(), &filter, &options, 99).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_hash_cache_detects_changes() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+    let cache_dir = tempfile::tempdir().context("failed to create temp dir")?;
+
+    let file = temp_dir.path().join("a.txt");
+    let past = std::time::SystemTime::now() - std::time::Duration::from_secs(60);
+
+    fs::write(&file, b"content").context("failed to write file")?;
+    fs::File::options()
+        .write(true)
+        .open(&file)?
+        .set_modified(past)?;
+
+    let filter = PathFilter::default();
+    let options = HashOptions {
+        cache_path: Some(cache_dir.path().join("hash-cache.json")),
+        ..HashOptions::default()
+    };
+
+    let cached = Hash::hash_path(temp_dir.path(), &filter, &options)?;
+
+    assert_eq!(cached, hash(temp_dir.path())?);
+    assert!(cache_dir.path().join("hash-cache.json").is_file());
+    assert_eq!(cached, Hash::hash_path(temp_dir.path(), &filter, &options)?);
+
+    fs::write(&file, b"modified content").context("failed to write file")?;
+
+    let modified = Hash::hash_path(temp_dir.path(), &filter, &options)?;
+    assert_ne!(cached, modified);
+
+    // same size and inode, only the mtime tells the content changed
+    fs::write(&file, b"modified CONTENT").context("failed to write file")?;
+    fs::File::options()
+        .write(true)
+        .open(&file)?
+        .set_modified(past + std::time::Duration::from_secs(1))?;
+
+    let rehashed = Hash::hash_path(temp_dir.path(), &filter, &options)?;
+    assert_ne!(modified, rehashed);
+    assert_eq!(rehashed, hash(temp_dir.path())?);
+
+    Ok(())
+}
+
+#[test]
+fn test_hash_algorithms() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        fs::write(temp_dir.path().join(name), name).context("failed to write file")?;
+    }
+
+    let filter = PathFilter::default();
+    let mut digests = Vec::new();
+
+    for algorithm in [
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Blake3,
+        HashAlgorithm::Xxh3,
+    ] {
+        let options = HashOptions {
+            algorithm,
+            ..HashOptions::default()
+        };
+
+        let digest = Hash::hash_path(temp_dir.path(), &filter, &options)?;
+
+        assert_eq!(digest, Hash::hash_path(temp_dir.path(), &filter, &options)?);
+        digests.push(digest);
+    }
+
+    assert_eq!(digests[0], hash(temp_dir.path())?);
+    assert_ne!(digests[0], digests[1]);
+    assert_ne!(digests[1], digests[2]);
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anyhow::Context;
+use rcloud::{
+    config::prelude::HookExecType,
+    utils::history::{History, HistoryEntry, SyncStatus},
+};
+
+fn entry(path_id: &str, timestamp: i64, status: SyncStatus) -> HistoryEntry {
+    HistoryEntry {
+        timestamp,
+        path_id: path_id.to_string(),
+        local_path: format!("/tmp/{}", path_id),
+        remote: format!("remote:{}", path_id),
+        direction: HookExecType::Push,
+        status,
+        hash: None,
+        error: None,
+        duration_ms: 0,
+    }
+}
+
+#[test]
+fn test_history_append_and_last_by_path() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+    let history = History::new(&temp_dir.path().join("history.jsonl"));
+
+    assert!(history.entries()?.is_empty());
+
+    history.append(&entry("a", 1, SyncStatus::Success))?;
+    history.append(&entry("b", 2, SyncStatus::Unchanged))?;
+    history.append(&entry("a", 3, SyncStatus::Failed))?;
+
+    assert_eq!(history.entries()?.len(), 3);
+
+    let last = history.last_by_path()?;
+
+    assert_eq!(last["a"].timestamp, 3);
+    assert_eq!(last["a"].status, SyncStatus::Failed);
+    assert_eq!(last["b"].status, SyncStatus::Unchanged);
+
+    Ok(())
+}
+
+#[test]
+fn test_history_last_synced_by_path() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+    let history = History::new(&temp_dir.path().join("history.jsonl"));
+
+    history.append(&entry("a", 1, SyncStatus::Success))?;
+    history.append(&entry("a", 2, SyncStatus::Unchanged))?;
+    history.append(&entry("a", 3, SyncStatus::Failed))?;
+    history.append(&entry("b", 4, SyncStatus::Aborted))?;
+
+    let last = history.last_synced_by_path()?;
+
+    assert_eq!(last["a"].timestamp, 2);
+    assert!(!last.contains_key("b"));
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+pub mod filter_test;
+pub mod hash_test;
+pub mod history_test;
+pub mod schedule_test;
@@ -0,0 +1,51 @@
+use chrono::{Local, TimeDelta, TimeZone};
+use rcloud::utils::schedule::{Schedule, parse_interval, parse_time};
+
+#[test]
+fn test_schedule_interval() -> anyhow::Result<()> {
+    let schedule = "every 1h30m".parse::<Schedule>()?;
+    let now = Local::now();
+
+    assert!(schedule.is_due(None, now)?);
+    assert!(!schedule.is_due(Some(now - TimeDelta::minutes(89)), now)?);
+    assert!(schedule.is_due(Some(now - TimeDelta::minutes(90)), now)?);
+
+    assert!("every 6x".parse::<Schedule>().is_err());
+    assert!("every 6".parse::<Schedule>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_schedule_cron() -> anyhow::Result<()> {
+    let schedule = "0 3 * * *".parse::<Schedule>()?;
+
+    let last_run = Local
+        .with_ymd_and_hms(2025, 1, 1, 3, 0, 0)
+        .single()
+        .expect("valid date");
+
+    assert_eq!(
+        schedule.next_run(Some(last_run), last_run)?,
+        last_run + TimeDelta::days(1)
+    );
+
+    assert!(!schedule.is_due(Some(last_run), last_run + TimeDelta::hours(23))?);
+    assert!(schedule.is_due(Some(last_run), last_run + TimeDelta::hours(24))?);
+    assert!("not a cron".parse::<Schedule>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_schedule_overflow() -> anyhow::Result<()> {
+    assert!(parse_interval("99999999999999w").is_err());
+    assert!(parse_interval("9223372036854775807s1s").is_err());
+    assert!(parse_time("99999999999999w").is_err());
+    assert!(parse_time("100000000w").is_err());
+
+    let schedule = "every 100000000w".parse::<Schedule>()?;
+    assert!(schedule.next_run(Some(Local::now()), Local::now()).is_err());
+
+    Ok(())
+}